@@ -0,0 +1,167 @@
+//! Compares the combinators against the `match`/`.await` code they replace.
+//!
+//! Each pair should report the same time; a gap means the named futures add
+//! overhead. Futures are driven by a no-op executor, so the numbers measure
+//! the state machines alone.
+
+use std::future::Future;
+use std::hint::black_box;
+use std::pin::pin;
+use std::task::{Context, Poll, Waker};
+
+use async_result_ext::AsyncResultExt;
+use criterion::{Criterion, criterion_group, criterion_main};
+
+fn block_on<F: Future>(fut: F) -> F::Output {
+    let mut fut = pin!(fut);
+    let mut cx = Context::from_waker(Waker::noop());
+    loop {
+        if let Poll::Ready(output) = fut.as_mut().poll(&mut cx) {
+            return output;
+        }
+    }
+}
+
+/// A step that stays pending once, so the futures under test are polled twice.
+async fn yield_now() {
+    let mut yielded = false;
+    std::future::poll_fn(|cx| {
+        if yielded {
+            Poll::Ready(())
+        } else {
+            yielded = true;
+            cx.waker().wake_by_ref();
+            Poll::Pending
+        }
+    })
+    .await
+}
+
+async fn double(v: u64) -> u64 {
+    yield_now().await;
+    v.wrapping_mul(2)
+}
+
+async fn checked(v: u64) -> Result<u64, u32> {
+    yield_now().await;
+    if v % 7 == 0 { Err(7) } else { Ok(v + 1) }
+}
+
+fn input(i: u64) -> Result<u64, u32> {
+    if i % 5 == 0 { Err(5) } else { Ok(i) }
+}
+
+fn bench_async_map(c: &mut Criterion) {
+    let mut group = c.benchmark_group("async_map");
+    group.bench_function("combinator", |b| {
+        let mut i = 0u64;
+        b.iter(|| {
+            i += 1;
+            block_on(black_box(input(i)).async_map(double))
+        })
+    });
+    group.bench_function("match", |b| {
+        let mut i = 0u64;
+        b.iter(|| {
+            i += 1;
+            block_on(async {
+                match black_box(input(i)) {
+                    Ok(v) => Ok(double(v).await),
+                    Err(e) => Err(e),
+                }
+            })
+        })
+    });
+    group.finish();
+}
+
+fn bench_async_and_then(c: &mut Criterion) {
+    let mut group = c.benchmark_group("async_and_then");
+    group.bench_function("combinator", |b| {
+        let mut i = 0u64;
+        b.iter(|| {
+            i += 1;
+            block_on(black_box(input(i)).async_and_then(checked))
+        })
+    });
+    group.bench_function("match", |b| {
+        let mut i = 0u64;
+        b.iter(|| {
+            i += 1;
+            block_on(async {
+                match black_box(input(i)) {
+                    Ok(v) => checked(v).await,
+                    Err(e) => Err(e),
+                }
+            })
+        })
+    });
+    group.finish();
+}
+
+fn bench_chain(c: &mut Criterion) {
+    let mut group = c.benchmark_group("chain");
+    group.bench_function("combinator", |b| {
+        let mut i = 0u64;
+        b.iter(|| {
+            i += 1;
+            block_on(async {
+                black_box(input(i))
+                    .async_map(double)
+                    .await
+                    .async_and_then(checked)
+                    .await
+                    .async_map_err(|e| async move { e * 2 })
+                    .await
+                    .async_or_else(|e| async move { if e == 10 { Ok(0) } else { Err(e) } })
+                    .await
+                    .async_and_then(checked)
+                    .await
+                    .async_map(double)
+                    .await
+            })
+        })
+    });
+    group.bench_function("match", |b| {
+        let mut i = 0u64;
+        b.iter(|| {
+            i += 1;
+            block_on(async {
+                let r = match black_box(input(i)) {
+                    Ok(v) => Ok(double(v).await),
+                    Err(e) => Err(e),
+                };
+                let r = match r {
+                    Ok(v) => checked(v).await,
+                    Err(e) => Err(e),
+                };
+                let r = match r {
+                    Ok(v) => Ok(v),
+                    Err(e) => Err(e * 2),
+                };
+                let r = match r {
+                    Ok(v) => Ok(v),
+                    Err(e) => {
+                        if e == 10 {
+                            Ok(0)
+                        } else {
+                            Err(e)
+                        }
+                    }
+                };
+                let r = match r {
+                    Ok(v) => checked(v).await,
+                    Err(e) => Err(e),
+                };
+                match r {
+                    Ok(v) => Ok(double(v).await),
+                    Err(e) => Err(e),
+                }
+            })
+        })
+    });
+    group.finish();
+}
+
+criterion_group!(benches, bench_async_map, bench_async_and_then, bench_chain);
+criterion_main!(benches);
@@ -0,0 +1,291 @@
+//! [`anyhow`] integration for results.
+//!
+//! Requires the `anyhow` feature, which implies `std`.
+
+use core::fmt;
+use core::future::Future;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+
+use ::anyhow::Error;
+use pin_project_lite::pin_project;
+
+use crate::result::AsyncMapErrInto;
+use crate::state::{State, Step};
+
+/// Asynchronous extensions for [`Result<T, E>`] that produce [`anyhow::Error`].
+///
+/// The errors involved can be any type `anyhow::Error` converts from, which
+/// covers every `std::error::Error + Send + Sync + 'static` as well as
+/// `anyhow::Error` itself. This saves a `.map_err(anyhow::Error::from)` per step.
+pub trait AsyncResultAnyhowExt<T, E> {
+    /// Like [`async_and_then`](crate::AsyncResultExt::async_and_then), converting
+    /// both the incoming error and the error of `op` into `anyhow::Error`.
+    ///
+    /// ```
+    /// use async_result_ext::anyhow::AsyncResultAnyhowExt;
+    ///
+    /// # futures::executor::block_on(async {
+    /// let r: Result<&str, std::str::Utf8Error> = Ok("42");
+    /// let res: anyhow::Result<i32> = r
+    ///     .async_and_then_anyhow(|s| async move { s.parse::<i32>() })
+    ///     .await;
+    /// assert_eq!(res.unwrap(), 42);
+    /// # });
+    /// ```
+    fn async_and_then_anyhow<U, O, F, Fut>(self, op: F) -> AsyncAndThenAnyhow<T, E, F, Fut>
+    where
+        Error: From<E> + From<O>,
+        F: FnOnce(T) -> Fut,
+        Fut: Future<Output = Result<U, O>>;
+
+    /// Like [`async_map_err`](crate::AsyncResultExt::async_map_err), converting the
+    /// mapped error into `anyhow::Error`.
+    ///
+    /// If the result is `Ok`, `op` is never called.
+    ///
+    /// ```
+    /// use async_result_ext::anyhow::AsyncResultAnyhowExt;
+    ///
+    /// # futures::executor::block_on(async {
+    /// let r: Result<(), u16> = Err(503);
+    /// let res = r
+    ///     .async_map_err_anyhow(|status| async move {
+    ///         std::io::Error::other(format!("upstream returned {status}"))
+    ///     })
+    ///     .await;
+    /// assert_eq!(res.unwrap_err().to_string(), "upstream returned 503");
+    /// # });
+    /// ```
+    fn async_map_err_anyhow<F, Fut, M>(self, op: F) -> AsyncMapErrInto<T, E, Error, F, Fut>
+    where
+        Error: From<M>,
+        F: FnOnce(E) -> Fut,
+        Fut: Future<Output = M>;
+
+    /// Wraps the error with context produced by awaiting `op`, like
+    /// [`anyhow::Context::context`].
+    ///
+    /// On `Err`, the error is converted into `anyhow::Error` and `op` is awaited
+    /// with a reference to it, so the context can be fetched asynchronously (for
+    /// example a request id looked up from a store). The original error stays
+    /// available as the source of the returned one. On `Ok`, `op` is never called.
+    ///
+    /// ```
+    /// use async_result_ext::anyhow::AsyncResultAnyhowExt;
+    ///
+    /// # futures::executor::block_on(async {
+    /// let r: Result<(), std::io::Error> = Err(std::io::Error::other("disk full"));
+    /// let err = r
+    ///     .async_context(|_| async { "while saving request 42" })
+    ///     .await
+    ///     .unwrap_err();
+    /// assert_eq!(format!("{err:#}"), "while saving request 42: disk full");
+    /// # });
+    /// ```
+    fn async_context<F, Fut, C>(self, op: F) -> AsyncContext<T, E, F, Fut>
+    where
+        Error: From<E>,
+        C: fmt::Display + Send + Sync + 'static,
+        F: FnOnce(&Error) -> Fut,
+        Fut: Future<Output = C>;
+}
+
+impl<T, E> AsyncResultAnyhowExt<T, E> for Result<T, E> {
+    fn async_and_then_anyhow<U, O, F, Fut>(self, op: F) -> AsyncAndThenAnyhow<T, E, F, Fut>
+    where
+        Error: From<E> + From<O>,
+        F: FnOnce(T) -> Fut,
+        Fut: Future<Output = Result<U, O>>,
+    {
+        AsyncAndThenAnyhow {
+            state: State::new((self, op)),
+        }
+    }
+
+    fn async_map_err_anyhow<F, Fut, M>(self, op: F) -> AsyncMapErrInto<T, E, Error, F, Fut>
+    where
+        Error: From<M>,
+        F: FnOnce(E) -> Fut,
+        Fut: Future<Output = M>,
+    {
+        AsyncMapErrInto::new(self, op)
+    }
+
+    fn async_context<F, Fut, C>(self, op: F) -> AsyncContext<T, E, F, Fut>
+    where
+        Error: From<E>,
+        C: fmt::Display + Send + Sync + 'static,
+        F: FnOnce(&Error) -> Fut,
+        Fut: Future<Output = C>,
+    {
+        AsyncContext {
+            state: State::new((self, op)),
+        }
+    }
+}
+
+pin_project! {
+    /// Future for the [`async_and_then_anyhow`](AsyncResultAnyhowExt::async_and_then_anyhow)
+    /// method.
+    #[must_use = "futures do nothing unless you `.await` or poll them"]
+    pub struct AsyncAndThenAnyhow<T, E, F, Fut> {
+        #[pin]
+        state: State<(Result<T, E>, F), Fut, ()>,
+    }
+}
+
+impl<T, E, U, O, F, Fut> Future for AsyncAndThenAnyhow<T, E, F, Fut>
+where
+    Error: From<E> + From<O>,
+    F: FnOnce(T) -> Fut,
+    Fut: Future<Output = Result<U, O>>,
+{
+    type Output = Result<U, Error>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        self.project().state.poll_with(
+            "async_and_then_anyhow",
+            cx,
+            |(this, op)| match this {
+                Ok(value) => Step::Run(op(value), ()),
+                Err(err) => Step::Ready(Err(Error::from(err))),
+            },
+            |output, ()| output.map_err(Error::from),
+        )
+    }
+}
+
+pin_project! {
+    /// Future for the [`async_context`](AsyncResultAnyhowExt::async_context) method.
+    #[must_use = "futures do nothing unless you `.await` or poll them"]
+    pub struct AsyncContext<T, E, F, Fut> {
+        #[pin]
+        state: State<(Result<T, E>, F), Fut, Error>,
+    }
+}
+
+impl<T, E, C, F, Fut> Future for AsyncContext<T, E, F, Fut>
+where
+    Error: From<E>,
+    C: fmt::Display + Send + Sync + 'static,
+    F: FnOnce(&Error) -> Fut,
+    Fut: Future<Output = C>,
+{
+    type Output = Result<T, Error>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        self.project().state.poll_with(
+            "async_context",
+            cx,
+            |(this, op)| match this {
+                Ok(value) => Step::Ready(Ok(value)),
+                Err(err) => {
+                    let err = Error::from(err);
+                    Step::Run(op(&err), err)
+                }
+            },
+            |context, err| Err(err.context(context)),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::never_polled;
+    use core::fmt;
+    use futures::executor::block_on;
+    use std::string::ToString;
+
+    #[derive(Debug, PartialEq)]
+    struct QuotaExceeded {
+        limit: u32,
+    }
+
+    impl fmt::Display for QuotaExceeded {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(f, "quota of {} exceeded", self.limit)
+        }
+    }
+
+    impl std::error::Error for QuotaExceeded {}
+
+    #[test]
+    fn test_async_and_then_anyhow() {
+        block_on(async {
+            let r: Result<u32, QuotaExceeded> = Ok(3);
+            let res = r
+                .async_and_then_anyhow(|v| async move { Ok::<_, Error>(v + 1) })
+                .await;
+            assert_eq!(res.unwrap(), 4);
+
+            let r: Result<u32, QuotaExceeded> = Ok(11);
+            let err = r
+                .async_and_then_anyhow(|v| async move {
+                    if v > 10 {
+                        Err(QuotaExceeded { limit: 10 })
+                    } else {
+                        Ok(v)
+                    }
+                })
+                .await
+                .unwrap_err();
+            assert_eq!(err.downcast_ref(), Some(&QuotaExceeded { limit: 10 }));
+
+            let r: Result<u32, QuotaExceeded> = Err(QuotaExceeded { limit: 5 });
+            let err = r
+                .async_and_then_anyhow(|_| never_polled::<Result<u32, Error>>())
+                .await
+                .unwrap_err();
+            assert_eq!(err.to_string(), "quota of 5 exceeded");
+            assert_eq!(
+                err.downcast::<QuotaExceeded>().unwrap(),
+                QuotaExceeded { limit: 5 }
+            );
+        });
+    }
+
+    #[test]
+    fn test_async_map_err_anyhow() {
+        block_on(async {
+            let r: Result<u32, u32> = Err(7);
+            let err = r
+                .async_map_err_anyhow(|limit| async move { QuotaExceeded { limit } })
+                .await
+                .unwrap_err();
+            assert_eq!(err.downcast_ref(), Some(&QuotaExceeded { limit: 7 }));
+
+            let r: Result<u32, u32> = Ok(1);
+            let res = r
+                .async_map_err_anyhow(|_| never_polled::<QuotaExceeded>())
+                .await;
+            assert_eq!(res.unwrap(), 1);
+        });
+    }
+
+    #[test]
+    fn test_async_context() {
+        block_on(async {
+            let r: Result<u32, QuotaExceeded> = Err(QuotaExceeded { limit: 3 });
+            let err = r
+                .async_context(|err| {
+                    let detail = err.to_string();
+                    async move { std::format!("request 7 ({detail})") }
+                })
+                .await
+                .unwrap_err();
+            assert_eq!(err.to_string(), "request 7 (quota of 3 exceeded)");
+            let chain: std::vec::Vec<_> = err.chain().map(ToString::to_string).collect();
+            assert_eq!(
+                chain,
+                ["request 7 (quota of 3 exceeded)", "quota of 3 exceeded"]
+            );
+            assert_eq!(err.downcast_ref(), Some(&QuotaExceeded { limit: 3 }));
+
+            let r: Result<u32, QuotaExceeded> = Ok(2);
+            let res = r.async_context(|_| never_polled::<&str>()).await;
+            assert_eq!(res.unwrap(), 2);
+        });
+    }
+}
@@ -0,0 +1,851 @@
+//! Running async operations, mostly fallible ones, over collections.
+//!
+//! The helpers collecting into a `Vec` require the `alloc` feature; the
+//! concurrent variants also require the `futures` feature.
+
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+use core::future::Future;
+
+#[cfg(feature = "futures")]
+use futures_util::stream::{FuturesUnordered, StreamExt};
+
+/// Maps every item with async `op` in order, stopping at the first error.
+///
+/// Items are processed sequentially: each call to `op` starts only after the
+/// previous future completed. On `Err`, the remaining items are dropped
+/// without being passed to `op`.
+///
+/// # Cancellation
+///
+/// Dropping the future drops the outputs collected so far, the pending call's
+/// future and the items not yet processed. Effects of completed calls remain.
+///
+/// ```
+/// use async_result_ext::collect::try_map_all;
+///
+/// # futures::executor::block_on(async {
+/// let res = try_map_all(vec!["1", "2", "3"], |s| async move { s.parse::<i32>() }).await;
+/// assert_eq!(res, Ok(vec![1, 2, 3]));
+/// # });
+/// ```
+#[cfg(feature = "alloc")]
+pub async fn try_map_all<T, U, E, F, Fut>(items: Vec<T>, mut op: F) -> Result<Vec<U>, E>
+where
+    F: FnMut(T) -> Fut,
+    Fut: Future<Output = Result<U, E>>,
+{
+    let mut mapped = Vec::with_capacity(items.len());
+    for item in items {
+        mapped.push(op(item).await?);
+    }
+    Ok(mapped)
+}
+
+/// Awaits `op` on each `Ok` item of `iter` in order, stopping at the first `Err`.
+///
+/// The iterator is consumed lazily, so items after the first `Err` are neither
+/// produced nor passed to `op`.
+///
+/// # Cancellation
+///
+/// Dropping the future drops the outputs collected so far and the pending
+/// call's future; the iterator is not advanced any further.
+///
+/// ```
+/// use async_result_ext::collect::collect_results;
+///
+/// # futures::executor::block_on(async {
+/// let lines = ["1", "2", "x", "4"].map(str::parse::<i32>);
+/// let res = collect_results(lines, |v| async move { v * 2 }).await;
+/// assert!(res.is_err());
+/// # });
+/// ```
+#[cfg(feature = "alloc")]
+pub async fn collect_results<I, T, E, U, F, Fut>(iter: I, mut op: F) -> Result<Vec<U>, E>
+where
+    I: IntoIterator<Item = Result<T, E>>,
+    F: FnMut(T) -> Fut,
+    Fut: Future<Output = U>,
+{
+    let iter = iter.into_iter();
+    let mut collected = Vec::with_capacity(iter.size_hint().0);
+    for item in iter {
+        collected.push(op(item?).await);
+    }
+    Ok(collected)
+}
+
+/// Like [`try_map_all`], but keeps up to `concurrency` futures in flight at once.
+///
+/// Outputs are returned in input order regardless of completion order. On the
+/// first error to complete, the in-flight futures are dropped and the remaining
+/// items are never passed to `op`. A `concurrency` of `0` behaves like `1`.
+///
+/// # Cancellation
+///
+/// Dropping the future drops every in-flight future at whatever await point
+/// it reached, along with the outputs completed so far. Items that have not
+/// started are never passed to `op`.
+///
+/// Requires the `futures` feature.
+///
+/// ```
+/// use async_result_ext::collect::try_map_all_concurrent;
+///
+/// # futures::executor::block_on(async {
+/// let res = try_map_all_concurrent(vec![1, 2, 3], 2, |v| async move { Ok::<_, ()>(v * 10) }).await;
+/// assert_eq!(res, Ok(vec![10, 20, 30]));
+/// # });
+/// ```
+#[cfg(feature = "futures")]
+pub async fn try_map_all_concurrent<T, U, E, F, Fut>(
+    items: Vec<T>,
+    concurrency: usize,
+    mut op: F,
+) -> Result<Vec<U>, E>
+where
+    F: FnMut(T) -> Fut,
+    Fut: Future<Output = Result<U, E>>,
+{
+    let concurrency = concurrency.max(1);
+    let mut slots: Vec<Option<U>> = Vec::with_capacity(items.len());
+    let mut items = items.into_iter().enumerate();
+    let mut in_flight = FuturesUnordered::new();
+
+    loop {
+        while in_flight.len() < concurrency {
+            let Some((index, item)) = items.next() else {
+                break;
+            };
+            slots.push(None);
+            let fut = op(item);
+            in_flight.push(async move { (index, fut.await) });
+        }
+
+        match in_flight.next().await {
+            Some((index, output)) => slots[index] = Some(output?),
+            None => break,
+        }
+    }
+
+    Ok(slots
+        .into_iter()
+        .map(|slot| slot.expect("every item completed"))
+        .collect())
+}
+
+/// Maps every element of an array with async `op` in order, stopping at the first error.
+///
+/// Like [`try_map_all`] for a fixed number of items, but returns a `[U; N]`
+/// without allocating. On `Err`, the elements mapped so far are dropped and the
+/// remaining ones are dropped without being passed to `op`; partially filled
+/// output is tracked with `Option` slots rather than uninitialized memory.
+///
+/// ```
+/// use async_result_ext::collect::try_map_array;
+///
+/// # futures::executor::block_on(async {
+/// let rgb = try_map_array(["ff", "80", "00"], |hex| async move { u8::from_str_radix(hex, 16) }).await;
+/// assert_eq!(rgb, Ok([255, 128, 0]));
+/// # });
+/// ```
+pub async fn try_map_array<const N: usize, T, U, E, F, Fut>(
+    arr: [T; N],
+    mut op: F,
+) -> Result<[U; N], E>
+where
+    F: FnMut(T) -> Fut,
+    Fut: Future<Output = Result<U, E>>,
+{
+    let mut slots: [Option<U>; N] = core::array::from_fn(|_| None);
+    for (slot, item) in slots.iter_mut().zip(arr) {
+        *slot = Some(op(item).await?);
+    }
+    Ok(slots.map(|slot| slot.expect("every element mapped")))
+}
+
+/// Splits an iterator of results into its `Ok` values and its `Err` values.
+///
+/// Unlike [`collect_results`], every item is drained, so all failures are
+/// collected instead of stopping at the first one. Both vectors keep input
+/// order. See [`partition_results_with`] to run an async hook per item.
+///
+/// ```
+/// use async_result_ext::collect::partition_results;
+///
+/// # futures::executor::block_on(async {
+/// let (ok, err) = partition_results(["1", "x", "3"].map(str::parse::<i32>)).await;
+/// assert_eq!(ok, [1, 3]);
+/// assert_eq!(err.len(), 1);
+/// # });
+/// ```
+#[cfg(feature = "alloc")]
+pub async fn partition_results<I, T, E>(iter: I) -> (Vec<T>, Vec<E>)
+where
+    I: IntoIterator<Item = Result<T, E>>,
+{
+    partition_results_with(iter, |_| core::future::ready(())).await
+}
+
+/// Like [`partition_results`], awaiting `op` on each item before it is classified.
+///
+/// `op` sees every item, `Ok` or `Err`, in order; each call starts only after
+/// the previous future completed.
+///
+/// ```
+/// use async_result_ext::collect::partition_results_with;
+///
+/// # futures::executor::block_on(async {
+/// let mut failures = 0;
+/// let batch = [Ok(1), Err("timeout"), Ok(2)];
+/// let (ok, err) = partition_results_with(batch, |item| {
+///     failures += item.is_err() as usize;
+///     async {}
+/// })
+/// .await;
+/// assert_eq!((ok, err, failures), (vec![1, 2], vec!["timeout"], 1));
+/// # });
+/// ```
+#[cfg(feature = "alloc")]
+pub async fn partition_results_with<I, T, E, F, Fut>(iter: I, mut op: F) -> (Vec<T>, Vec<E>)
+where
+    I: IntoIterator<Item = Result<T, E>>,
+    F: FnMut(&Result<T, E>) -> Fut,
+    Fut: Future<Output = ()>,
+{
+    let (mut oks, mut errs) = (Vec::new(), Vec::new());
+    for item in iter {
+        op(&item).await;
+        match item {
+            Ok(value) => oks.push(value),
+            Err(err) => errs.push(err),
+        }
+    }
+    (oks, errs)
+}
+
+/// Folds `iter` with async fallible `op`, stopping at the first error.
+///
+/// Items are folded sequentially: each step starts only after the previous
+/// future completed and receives its accumulator. On `Err`, the remaining
+/// items are neither produced nor passed to `op`. An empty iterator yields
+/// `Ok(init)`.
+///
+/// # Cancellation
+///
+/// Dropping the future drops the pending step's future, and with it the
+/// accumulator it took; the iterator is not advanced any further.
+///
+/// ```
+/// use async_result_ext::collect::try_fold;
+///
+/// # futures::executor::block_on(async {
+/// let sum = try_fold(["1", "2", "3"], 0, |acc, s| async move {
+///     Ok::<_, core::num::ParseIntError>(acc + s.parse::<i32>()?)
+/// })
+/// .await;
+/// assert_eq!(sum, Ok(6));
+/// # });
+/// ```
+pub async fn try_fold<I, T, A, E, F, Fut>(iter: I, init: A, mut op: F) -> Result<A, E>
+where
+    I: IntoIterator<Item = T>,
+    F: FnMut(A, T) -> Fut,
+    Fut: Future<Output = Result<A, E>>,
+{
+    let mut acc = init;
+    for item in iter {
+        acc = op(acc, item).await?;
+    }
+    Ok(acc)
+}
+
+/// Reduces `iter` to a single value by combining items with async `op`.
+///
+/// The first item seeds the accumulator and every following item is combined
+/// with it in order, each step starting only after the previous future
+/// completed. An empty iterator yields `None`; a single item is returned as
+/// is, without calling `op`. Use [`try_fold`] when an initial accumulator is
+/// available or `op` can fail.
+///
+/// ```
+/// use async_result_ext::collect::reduce;
+///
+/// # futures::executor::block_on(async {
+/// let max = reduce([3, 9, 4], |a, b| async move { a.max(b) }).await;
+/// assert_eq!(max, Some(9));
+/// # });
+/// ```
+pub async fn reduce<I, T, F, Fut>(iter: I, mut op: F) -> Option<T>
+where
+    I: IntoIterator<Item = T>,
+    F: FnMut(T, T) -> Fut,
+    Fut: Future<Output = T>,
+{
+    let mut iter = iter.into_iter();
+    let mut acc = iter.next()?;
+    for item in iter {
+        acc = op(acc, item).await;
+    }
+    Some(acc)
+}
+
+/// Like [`reduce`], but with a fallible `op`, stopping at the first error.
+///
+/// On `Err`, the remaining items are neither produced nor passed to `op`. An
+/// empty iterator yields `Ok(None)`.
+///
+/// ```
+/// use async_result_ext::collect::try_reduce;
+///
+/// # futures::executor::block_on(async {
+/// let sum = try_reduce([1u8, 2, 3], |a, b| async move { a.checked_add(b).ok_or("overflow") }).await;
+/// assert_eq!(sum, Ok(Some(6)));
+/// # });
+/// ```
+pub async fn try_reduce<I, T, E, F, Fut>(iter: I, mut op: F) -> Result<Option<T>, E>
+where
+    I: IntoIterator<Item = T>,
+    F: FnMut(T, T) -> Fut,
+    Fut: Future<Output = Result<T, E>>,
+{
+    let mut iter = iter.into_iter();
+    let Some(mut acc) = iter.next() else {
+        return Ok(None);
+    };
+    for item in iter {
+        acc = op(acc, item).await?;
+    }
+    Ok(Some(acc))
+}
+
+/// Returns whether async `pred` holds for every item of `iter`.
+///
+/// Items are tested sequentially, stopping at the first `false`; the remaining
+/// items are neither produced nor passed to `pred`. An empty iterator yields
+/// `true`, like [`Iterator::all`].
+///
+/// ```
+/// use async_result_ext::collect::all;
+///
+/// # futures::executor::block_on(async {
+/// let healthy = all(["db", "cache"], |name| async move { !name.is_empty() }).await;
+/// assert!(healthy);
+/// # });
+/// ```
+pub async fn all<I, T, F, Fut>(iter: I, mut pred: F) -> bool
+where
+    I: IntoIterator<Item = T>,
+    F: FnMut(T) -> Fut,
+    Fut: Future<Output = bool>,
+{
+    for item in iter {
+        if !pred(item).await {
+            return false;
+        }
+    }
+    true
+}
+
+/// Returns whether async `pred` holds for any item of `iter`.
+///
+/// Items are tested sequentially, stopping at the first `true`; the remaining
+/// items are neither produced nor passed to `pred`. An empty iterator yields
+/// `false`, like [`Iterator::any`].
+///
+/// ```
+/// use async_result_ext::collect::any;
+///
+/// # futures::executor::block_on(async {
+/// let has_admin = any(["guest", "admin"], |role| async move { role == "admin" }).await;
+/// assert!(has_admin);
+/// # });
+/// ```
+pub async fn any<I, T, F, Fut>(iter: I, mut pred: F) -> bool
+where
+    I: IntoIterator<Item = T>,
+    F: FnMut(T) -> Fut,
+    Fut: Future<Output = bool>,
+{
+    for item in iter {
+        if pred(item).await {
+            return true;
+        }
+    }
+    false
+}
+
+/// Returns the first item of `iter` for which async `pred` holds.
+///
+/// Items are tested sequentially, and `pred` gets a reference to each, like
+/// [`Iterator::find`]. Once an item matches, it is returned without testing or
+/// producing any further items. Returns `None` if no item matches.
+///
+/// ```
+/// use async_result_ext::collect::find;
+///
+/// # futures::executor::block_on(async {
+/// let free = find([8080, 8081, 8082], |port| {
+///     let port = *port;
+///     async move { port != 8080 }
+/// })
+/// .await;
+/// assert_eq!(free, Some(8081));
+/// # });
+/// ```
+pub async fn find<I, T, F, Fut>(iter: I, mut pred: F) -> Option<T>
+where
+    I: IntoIterator<Item = T>,
+    F: FnMut(&T) -> Fut,
+    Fut: Future<Output = bool>,
+{
+    for item in iter {
+        if pred(&item).await {
+            return Some(item);
+        }
+    }
+    None
+}
+
+/// Like [`find`], but with a fallible `pred`, stopping at the first error.
+///
+/// Returns `Ok(Some(item))` for the first match, `Ok(None)` if no item matches,
+/// or the first `Err` returned by `pred`; in both early cases, no further items
+/// are produced or tested.
+///
+/// ```
+/// use async_result_ext::collect::try_find;
+///
+/// # futures::executor::block_on(async {
+/// let first_big = try_find(["3", "40", "x"], |s| {
+///     let parsed = s.parse::<i32>();
+///     async move { Ok::<_, core::num::ParseIntError>(parsed? > 10) }
+/// })
+/// .await;
+/// assert_eq!(first_big, Ok(Some("40")));
+/// # });
+/// ```
+pub async fn try_find<I, T, E, F, Fut>(iter: I, mut pred: F) -> Result<Option<T>, E>
+where
+    I: IntoIterator<Item = T>,
+    F: FnMut(&T) -> Fut,
+    Fut: Future<Output = Result<bool, E>>,
+{
+    for item in iter {
+        if pred(&item).await? {
+            return Ok(Some(item));
+        }
+    }
+    Ok(None)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::{poll_once, yield_now};
+    use core::cell::Cell;
+    use core::pin::pin;
+    use futures::executor::block_on;
+    #[cfg(feature = "alloc")]
+    use std::vec;
+    use std::vec::Vec;
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn test_try_map_all_ok() {
+        block_on(async {
+            let res: Result<_, &str> =
+                try_map_all(vec![1, 2, 3], |v| async move { Ok(v * 2) }).await;
+            assert_eq!(res, Ok(vec![2, 4, 6]));
+        });
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn test_try_map_all_short_circuits() {
+        block_on(async {
+            let mut seen = Vec::new();
+            let res = try_map_all(vec![1, 2, 3, 4], |v| {
+                seen.push(v);
+                async move { if v == 2 { Err("two") } else { Ok(v) } }
+            })
+            .await;
+            assert_eq!(res, Err("two"));
+            assert_eq!(seen, [1, 2]);
+        });
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn test_try_map_all_empty() {
+        block_on(async {
+            let res: Result<Vec<i32>, &str> =
+                try_map_all(Vec::<i32>::new(), |_| async { unreachable!() }).await;
+            assert_eq!(res, Ok(Vec::new()));
+        });
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn test_collect_results() {
+        block_on(async {
+            let items = vec![Ok(1), Ok(2), Ok(3)];
+            let res: Result<_, &str> = collect_results(items, |v| async move { v + 1 }).await;
+            assert_eq!(res, Ok(vec![2, 3, 4]));
+        });
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn test_collect_results_stops_at_error() {
+        block_on(async {
+            let mut produced = 0;
+            let items = [Ok(1), Err("bad"), Ok(3)]
+                .into_iter()
+                .inspect(|_| produced += 1);
+            let mut seen = Vec::new();
+            let res = collect_results(items, |v| {
+                seen.push(v);
+                async move { v }
+            })
+            .await;
+            assert_eq!(res, Err("bad"));
+            assert_eq!(seen, [1]);
+            assert_eq!(produced, 2);
+        });
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn test_partition_results() {
+        block_on(async {
+            let items = vec![Ok(1), Err("a"), Ok(2), Err("b"), Ok(3)];
+            let (ok, err) = partition_results(items).await;
+            assert_eq!(ok, [1, 2, 3]);
+            assert_eq!(err, ["a", "b"]);
+        });
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn test_partition_results_with_runs_hook_per_item() {
+        block_on(async {
+            let items = vec![Err("a"), Ok(1), Err("b")];
+            let mut seen = Vec::new();
+            let (ok, err) = partition_results_with(items, |item| {
+                seen.push(*item);
+                async {}
+            })
+            .await;
+            assert_eq!(ok, [1]);
+            assert_eq!(err, ["a", "b"]);
+            assert_eq!(seen, [Err("a"), Ok(1), Err("b")]);
+        });
+    }
+
+    #[test]
+    fn test_try_map_array() {
+        block_on(async {
+            let res: Result<_, &str> = try_map_array([1, 2, 3], |v| async move { Ok(v * 2) }).await;
+            assert_eq!(res, Ok([2, 4, 6]));
+
+            let res: Result<[i32; 0], &str> =
+                try_map_array([], |_: i32| async { unreachable!() }).await;
+            assert_eq!(res, Ok([]));
+        });
+    }
+
+    #[test]
+    fn test_try_map_array_drops_on_error() {
+        use crate::tests::DropFlag;
+
+        block_on(async {
+            let mapped = [Cell::new(false), Cell::new(false)];
+            let unmapped_dropped = Cell::new(false);
+            let mut seen = Vec::new();
+            let res = try_map_array([0, 1, 2, 3], |i| {
+                seen.push(i);
+                let mapped = &mapped;
+                async move {
+                    match i {
+                        0 | 1 => Ok(DropFlag(&mapped[i])),
+                        _ => Err("two"),
+                    }
+                }
+            })
+            .await;
+            assert!(matches!(res, Err("two")));
+            assert_eq!(seen, [0, 1, 2]);
+            assert!(mapped.iter().all(Cell::get));
+
+            let res = try_map_array([None, Some(DropFlag(&unmapped_dropped))], |item| {
+                assert!(item.is_none(), "op must stop after the first error");
+                async { Err::<(), _>("first") }
+            })
+            .await;
+            assert_eq!(res, Err("first"));
+            assert!(unmapped_dropped.get());
+        });
+    }
+
+    #[test]
+    fn test_try_fold() {
+        block_on(async {
+            let res: Result<_, &str> =
+                try_fold(1..=4, 0, |acc, v| async move { Ok(acc * 10 + v) }).await;
+            assert_eq!(res, Ok(1234));
+        });
+    }
+
+    #[test]
+    fn test_try_fold_short_circuits() {
+        block_on(async {
+            let mut produced = 0;
+            let items = [1, 2, 3, 4].into_iter().inspect(|_| produced += 1);
+            let mut seen = Vec::new();
+            let res = try_fold(items, 0, |acc, v| {
+                seen.push(v);
+                async move { if v == 2 { Err("two") } else { Ok(acc + v) } }
+            })
+            .await;
+            assert_eq!(res, Err("two"));
+            assert_eq!(seen, [1, 2]);
+            assert_eq!(produced, 2);
+        });
+    }
+
+    #[test]
+    fn test_try_fold_empty() {
+        block_on(async {
+            let res: Result<_, &str> = try_fold(core::iter::empty::<i32>(), 7, |_, _| async {
+                unreachable!()
+            })
+            .await;
+            assert_eq!(res, Ok(7));
+        });
+    }
+
+    #[test]
+    fn test_reduce() {
+        block_on(async {
+            let mut calls = Vec::new();
+            let res = reduce([1, 2, 3, 4], |a, b| {
+                calls.push((a, b));
+                async move { a * 10 + b }
+            })
+            .await;
+            assert_eq!(res, Some(1234));
+            assert_eq!(calls, [(1, 2), (12, 3), (123, 4)]);
+        });
+    }
+
+    #[test]
+    fn test_reduce_single_and_empty() {
+        block_on(async {
+            let res = reduce([5], |_, _| async { unreachable!() }).await;
+            assert_eq!(res, Some(5));
+
+            let res = reduce(core::iter::empty::<i32>(), |_, _| async { unreachable!() }).await;
+            assert_eq!(res, None);
+        });
+    }
+
+    #[test]
+    fn test_try_reduce() {
+        block_on(async {
+            let res: Result<_, &str> = try_reduce([1, 2, 3], |a, b| async move { Ok(a + b) }).await;
+            assert_eq!(res, Ok(Some(6)));
+
+            let res: Result<_, &str> =
+                try_reduce(core::iter::empty::<i32>(), |_, _| async { unreachable!() }).await;
+            assert_eq!(res, Ok(None));
+        });
+    }
+
+    #[test]
+    fn test_try_reduce_short_circuits() {
+        block_on(async {
+            let mut produced = 0;
+            let items = [1, 2, 3, 4].into_iter().inspect(|_| produced += 1);
+            let mut seen = Vec::new();
+            let res = try_reduce(items, |a, b| {
+                seen.push(b);
+                async move { if b == 3 { Err("three") } else { Ok(a + b) } }
+            })
+            .await;
+            assert_eq!(res, Err("three"));
+            assert_eq!(seen, [2, 3]);
+            assert_eq!(produced, 3);
+        });
+    }
+
+    #[test]
+    fn test_all_short_circuits() {
+        block_on(async {
+            let res = all([2, 4, 5, 6], |v| async move {
+                assert_ne!(v, 6, "all must stop at the first false");
+                v % 2 == 0
+            })
+            .await;
+            assert!(!res);
+
+            assert!(all([2, 4], |v| async move { v % 2 == 0 }).await);
+            assert!(all(core::iter::empty::<i32>(), |_| async { unreachable!() }).await);
+        });
+    }
+
+    #[test]
+    fn test_any_short_circuits() {
+        block_on(async {
+            let res = any([1, 3, 4, 5], |v| async move {
+                assert_ne!(v, 5, "any must stop at the first true");
+                yield_now().await;
+                v % 2 == 0
+            })
+            .await;
+            assert!(res);
+
+            assert!(!any([1, 3], |v| async move { v % 2 == 0 }).await);
+            assert!(!any(core::iter::empty::<i32>(), |_| async { unreachable!() }).await);
+        });
+    }
+
+    #[test]
+    fn test_find() {
+        block_on(async {
+            let calls = Cell::new(0);
+            let is_even = |v: &i32| {
+                calls.set(calls.get() + 1);
+                let v = *v;
+                async move { v % 2 == 0 }
+            };
+            assert_eq!(find([1, 2, 3, 4], is_even).await, Some(2));
+            assert_eq!(calls.replace(0), 2, "found early");
+            assert_eq!(find([1, 3, 5, 6], is_even).await, Some(6));
+            assert_eq!(calls.replace(0), 4, "found last");
+            assert_eq!(find([1, 3, 5], is_even).await, None);
+            assert_eq!(calls.replace(0), 3, "not found");
+        });
+    }
+
+    #[test]
+    fn test_try_find() {
+        block_on(async {
+            let calls = Cell::new(0);
+            let check = |v: &i32| {
+                calls.set(calls.get() + 1);
+                let v = *v;
+                async move { if v < 0 { Err("negative") } else { Ok(v > 10) } }
+            };
+            assert_eq!(try_find([1, 20, 30], check).await, Ok(Some(20)));
+            assert_eq!(calls.replace(0), 2, "match");
+            assert_eq!(try_find([1, 2, 3], check).await, Ok(None));
+            assert_eq!(calls.replace(0), 3, "no match");
+            assert_eq!(try_find([1, -1, 20], check).await, Err("negative"));
+            assert_eq!(calls.replace(0), 2, "error during predicate");
+        });
+    }
+
+    #[test]
+    fn test_cancel_try_fold_stops_iteration() {
+        let mut produced = 0;
+        {
+            let items = [1, 2, 3].into_iter().inspect(|_| produced += 1);
+            let mut fut = pin!(try_fold(items, 0, |acc, v| async move {
+                yield_now().await;
+                Ok::<_, ()>(acc + v)
+            }));
+            assert!(poll_once(fut.as_mut()).is_pending());
+            assert!(poll_once(fut.as_mut()).is_pending());
+        }
+        assert_eq!(produced, 2);
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn test_cancel_try_map_all_drops_pending_call() {
+        let finished = Cell::new(0);
+        {
+            let mut fut = pin!(try_map_all(vec![1, 2, 3], |v| {
+                let finished = &finished;
+                async move {
+                    yield_now().await;
+                    finished.set(finished.get() + 1);
+                    Ok::<_, ()>(v)
+                }
+            }));
+            assert!(poll_once(fut.as_mut()).is_pending());
+            assert!(poll_once(fut.as_mut()).is_pending());
+        }
+        assert_eq!(finished.get(), 1);
+    }
+
+    #[cfg(feature = "futures")]
+    mod concurrent {
+        use super::*;
+        use crate::tests::after;
+        use core::cell::Cell;
+
+        #[test]
+        fn test_try_map_all_concurrent_preserves_order() {
+            block_on(async {
+                let active = Cell::new(0);
+                let peak = Cell::new(0);
+                let res: Result<_, &str> =
+                    try_map_all_concurrent(vec![5, 1, 4, 2, 3], 2, |ticks| {
+                        let (active, peak) = (&active, &peak);
+                        async move {
+                            active.set(active.get() + 1);
+                            peak.set(peak.get().max(active.get()));
+                            after(ticks).await;
+                            active.set(active.get() - 1);
+                            Ok(ticks * 10)
+                        }
+                    })
+                    .await;
+                assert_eq!(res, Ok(vec![50, 10, 40, 20, 30]));
+                assert_eq!(peak.get(), 2);
+            });
+        }
+
+        #[test]
+        fn test_try_map_all_concurrent_stops_on_error() {
+            block_on(async {
+                let mut started = Vec::new();
+                let res = try_map_all_concurrent(vec![3, 1, 2, 4, 5], 2, |v| {
+                    started.push(v);
+                    async move {
+                        after(v).await;
+                        if v == 1 { Err("one") } else { Ok(v) }
+                    }
+                })
+                .await;
+                assert_eq!(res, Err("one"));
+                assert_eq!(started, [3, 1]);
+            });
+        }
+
+        #[test]
+        fn test_cancel_try_map_all_concurrent_drops_in_flight() {
+            use crate::tests::DropFlag;
+
+            let dropped = [Cell::new(false), Cell::new(false)];
+            let mut started = Vec::new();
+            {
+                let mut fut = pin!(try_map_all_concurrent(vec![0, 1, 2, 3], 2, |i| {
+                    started.push(i);
+                    let flag = dropped.get(i).map(DropFlag);
+                    async move {
+                        let _flag = flag;
+                        after(10).await;
+                        Ok::<_, ()>(i)
+                    }
+                }));
+                assert!(poll_once(fut.as_mut()).is_pending());
+            }
+            assert_eq!(started, [0, 1]);
+            assert!(dropped.iter().all(Cell::get));
+        }
+    }
+}
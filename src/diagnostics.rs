@@ -0,0 +1,95 @@
+//! Helpers for debugging how futures are driven.
+//!
+//! Requires the `diagnostics` feature.
+
+use core::future::Future;
+use core::pin::Pin;
+use core::task::{Context, Poll, ready};
+
+use pin_project_lite::pin_project;
+
+use crate::state::polled_after_completion;
+
+/// Wraps `fut`, counting how many times it is polled until it completes.
+///
+/// The output is paired with the number of `poll` calls, including the final
+/// one that returned `Ready`. A count far above the number of genuine wake-ups
+/// points at a future that busy-polls.
+///
+/// ```
+/// use async_result_ext::AsyncResultExt;
+/// use async_result_ext::diagnostics::count_polls;
+///
+/// # futures::executor::block_on(async {
+/// let r: Result<i32, &str> = Ok(2);
+/// let (res, polls) = count_polls(r.async_map(|v| async move { v * 2 })).await;
+/// assert_eq!(res, Ok(4));
+/// assert_eq!(polls, 1);
+/// # });
+/// ```
+pub fn count_polls<F: Future>(fut: F) -> CountPolls<F> {
+    CountPolls {
+        fut,
+        polls: 0,
+        done: false,
+    }
+}
+
+pin_project! {
+    /// Future for the [`count_polls`] function.
+    #[must_use = "futures do nothing unless you `.await` or poll them"]
+    pub struct CountPolls<F> {
+        #[pin]
+        fut: F,
+        polls: usize,
+        done: bool,
+    }
+}
+
+impl<F> CountPolls<F> {
+    /// Returns how many times the wrapped future has been polled so far.
+    pub fn polls(&self) -> usize {
+        self.polls
+    }
+}
+
+impl<F: Future> Future for CountPolls<F> {
+    type Output = (F::Output, usize);
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.project();
+        if *this.done {
+            polled_after_completion("count_polls");
+        }
+
+        *this.polls += 1;
+        let output = ready!(this.fut.poll(cx));
+        *this.done = true;
+        Poll::Ready((output, *this.polls))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::{after, poll_once};
+    use core::pin::pin;
+    use futures::executor::block_on;
+
+    #[test]
+    fn test_count_polls() {
+        block_on(async {
+            assert_eq!(count_polls(core::future::ready(1)).await, (1, 1));
+            assert_eq!(count_polls(after(4)).await, ((), 5));
+        });
+    }
+
+    #[test]
+    fn test_count_polls_in_progress() {
+        let mut fut = pin!(count_polls(after(2)));
+        assert!(poll_once(fut.as_mut()).is_pending());
+        assert!(poll_once(fut.as_mut()).is_pending());
+        assert_eq!(fut.polls(), 2);
+        assert_eq!(poll_once(fut.as_mut()), Poll::Ready(((), 3)));
+    }
+}
@@ -0,0 +1,103 @@
+//! Merging independent results.
+//!
+//! These are free functions rather than methods because neither input is more
+//! natural as the receiver. When more than one input is `Err`, the leftmost
+//! error wins.
+
+use core::future::{Future, Ready, ready};
+
+/// Awaits `op` on both `Ok` values, merging two results of different types.
+///
+/// If either result is `Err`, returns the first error in argument order (`a`
+/// before `b`) without calling `op`; the other input is dropped. This is the
+/// `Result` analog of [`async_zip_with`](crate::AsyncOptionExt::async_zip_with).
+///
+/// ```
+/// use async_result_ext::join::join_with;
+///
+/// # futures::executor::block_on(async {
+/// let user: Result<&str, &str> = Ok("ada");
+/// let visits: Result<u32, &str> = Ok(3);
+/// let line = join_with(user, visits, |u, n| async move { format!("{u}: {n}") }).await;
+/// assert_eq!(line.as_deref(), Ok("ada: 3"));
+/// # });
+/// ```
+pub async fn join_with<T1, T2, E, R, F, Fut>(
+    a: Result<T1, E>,
+    b: Result<T2, E>,
+    op: F,
+) -> Result<R, E>
+where
+    F: FnOnce(T1, T2) -> Fut,
+    Fut: Future<Output = R>,
+{
+    let (a, b) = (a?, b?);
+    Ok(op(a, b).await)
+}
+
+/// Pairs the `Ok` values of two results.
+///
+/// Returns `Ok((a, b))` when both are `Ok`, otherwise the first error in
+/// argument order (`a` before `b`). Completes immediately; see
+/// [`AsyncResultExt`](crate::AsyncResultExt#methods-that-complete-immediately).
+///
+/// ```
+/// use async_result_ext::join::async_zip;
+///
+/// # futures::executor::block_on(async {
+/// let pair = async_zip(Ok::<_, &str>(1), Ok("one")).await;
+/// assert_eq!(pair, Ok((1, "one")));
+/// # });
+/// ```
+pub fn async_zip<T1, T2, E>(a: Result<T1, E>, b: Result<T2, E>) -> Ready<Result<(T1, T2), E>> {
+    ready(a.and_then(|a| Ok((a, b?))))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::never_polled;
+    use futures::executor::block_on;
+
+    #[test]
+    fn test_join_with_both_ok() {
+        block_on(async {
+            let res: Result<_, &str> =
+                join_with(Ok(2), Ok("x"), |n, s| async move { s.repeat(n) }).await;
+            assert_eq!(res.as_deref(), Ok("xx"));
+        });
+    }
+
+    #[test]
+    fn test_join_with_errors_are_left_biased() {
+        block_on(async {
+            let res: Result<i32, _> =
+                join_with(Err("left"), Ok(1), |_: i32, _| never_polled()).await;
+            assert_eq!(res, Err("left"));
+
+            let res: Result<i32, _> =
+                join_with(Ok(1), Err("right"), |_, _: i32| never_polled()).await;
+            assert_eq!(res, Err("right"));
+
+            let res: Result<i32, &str> =
+                join_with(Err("left"), Err("right"), |_: i32, _: i32| never_polled()).await;
+            assert_eq!(res, Err("left"));
+        });
+    }
+
+    #[test]
+    fn test_async_zip() {
+        block_on(async {
+            assert_eq!(async_zip(Ok::<_, &str>(1), Ok('a')).await, Ok((1, 'a')));
+            assert_eq!(async_zip(Err::<i32, _>("left"), Ok('a')).await, Err("left"));
+            assert_eq!(
+                async_zip(Ok(1), Err::<char, _>("right")).await,
+                Err("right")
+            );
+            assert_eq!(
+                async_zip::<i32, char, _>(Err("left"), Err("right")).await,
+                Err("left")
+            );
+        });
+    }
+}
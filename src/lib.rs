@@ -1,4 +1,50 @@
-use std::future::Future;
+#![no_std]
+
+#[cfg(feature = "alloc")]
+extern crate alloc;
+#[cfg(any(feature = "std", test))]
+extern crate std;
+
+use core::fmt;
+use core::future::{Future, Ready, ready};
+use core::ops::Sub;
+use core::time::Duration;
+
+#[cfg(feature = "anyhow")]
+pub mod anyhow;
+pub mod collect;
+#[cfg(feature = "diagnostics")]
+pub mod diagnostics;
+pub mod join;
+mod macros;
+pub mod option;
+pub mod pipeline;
+pub mod prelude;
+pub mod result;
+pub mod retry;
+#[cfg(feature = "tokio")]
+pub mod spawn;
+mod state;
+#[cfg(feature = "stream")]
+pub mod stream;
+pub mod timeout;
+#[cfg(feature = "tracing")]
+pub mod trace;
+
+pub use option::AsyncOptionExt;
+#[cfg(all(feature = "futures", feature = "std"))]
+use result::AsyncAndThenCatch;
+#[cfg(feature = "futures")]
+use result::AsyncSplit;
+use result::{
+    AsyncAnd, AsyncAndThen, AsyncAndThenCtx, AsyncAndThenInto, AsyncAndThenRef, AsyncAndThenRetry,
+    AsyncAndThenTimeout, AsyncContains, AsyncContainsErr, AsyncExpect, AsyncFilterMap,
+    AsyncFlattenErr, AsyncInspect, AsyncInspectErr, AsyncInspectErrRef, AsyncInspectRef,
+    AsyncInspectTry, AsyncIsErrAnd, AsyncIsOkAnd, AsyncMap, AsyncMapBoth, AsyncMapErr,
+    AsyncMapErrInspect, AsyncMapErrInto, AsyncMapOr, AsyncMapOrDefault, AsyncMapOrElse,
+    AsyncMapOrElseConcurrent, AsyncMapTimed, AsyncOr, AsyncOrDefaultWith, AsyncOrElse,
+    AsyncOrElseInto, AsyncReplaceErr, AsyncTap, AsyncThenUnit, AsyncTryMapBoth, AsyncUnwrapOrElse,
+};
 
 /// Asynchronous extensions for [`Result<T, E>`].
 ///
@@ -9,31 +55,52 @@ use std::future::Future;
 /// ```
 /// use async_result_ext::AsyncResultExt;
 ///
-/// #[tokio::main]
-/// async fn main() {
+/// // Any executor works; this example uses `futures`.
+/// futures::executor::block_on(async {
 ///     let r: Result<i32, &str> = Ok(2);
 ///
 ///     let doubled = r.async_map(|v| async move { v * 2 }).await;
 ///     assert_eq!(doubled, Ok(4));
-/// }
+/// });
 /// ```
+///
+/// # Cancellation
+///
+/// Dropping any of the returned futures, before its first poll or at any await
+/// point, is safe. The captured result, the unused closures and the future
+/// built by the closure that ran are all dropped with it, and no closure is
+/// called afterwards. Nothing is undone: effects the closure already performed
+/// remain. Methods that behave differently document it in their own
+/// `# Cancellation` section.
+///
+/// # Methods that complete immediately
+///
+/// A few methods, such as [`async_ok`](Self::async_ok) and
+/// [`async_unwrap_or_default`](Self::async_unwrap_or_default), mirror a
+/// conversion that has nothing to await. They still return a future, an
+/// already completed [`Ready`], so the conversion can sit in a chain of awaited
+/// combinators, e.g. before continuing with [`AsyncOptionExt`].
 pub trait AsyncResultExt<T, E> {
     /// Asynchronous version of [`Result::map`].
     ///
     /// Applies an async function `op` to the `Ok` value.
     /// If the result is `Err`, it is returned unchanged.
     ///
+    /// The output of `op` is wrapped as is, so an `op` that itself returns a
+    /// `Result` yields a nested `Result<Result<U, E>, E>`. Use
+    /// [`async_map_then`](AsyncResultExt::async_map_then) (or `async_and_then`)
+    /// for such fallible steps.
+    ///
     /// ```
     /// use async_result_ext::AsyncResultExt;
     ///
-    /// # #[tokio::main]
-    /// # async fn main() {
+    /// # futures::executor::block_on(async {
     /// let r: Result<i32, &str> = Ok(5);
     /// let res = r.async_map(|v| async move { v + 1 }).await;
     /// assert_eq!(res, Ok(6));
-    /// # }
+    /// # });
     /// ```
-    fn async_map<U, F, Fut>(self, op: F) -> impl Future<Output = Result<U, E>>
+    fn async_map<U, F, Fut>(self, op: F) -> AsyncMap<T, E, F, Fut>
     where
         F: FnOnce(T) -> Fut,
         Fut: Future<Output = U>;
@@ -45,14 +112,13 @@ pub trait AsyncResultExt<T, E> {
     /// ```
     /// use async_result_ext::AsyncResultExt;
     ///
-    /// # #[tokio::main]
-    /// # async fn main() {
+    /// # futures::executor::block_on(async {
     /// let r: Result<i32, &str> = Ok(2);
     /// let res = r.async_and_then(|v| async move { Ok(v * 3) }).await;
     /// assert_eq!(res, Ok(6));
-    /// # }
+    /// # });
     /// ```
-    fn async_and_then<U, F, Fut>(self, op: F) -> impl Future<Output = Result<U, E>>
+    fn async_and_then<U, F, Fut>(self, op: F) -> AsyncAndThen<T, E, F, Fut>
     where
         F: FnOnce(T) -> Fut,
         Fut: Future<Output = Result<U, E>>;
@@ -61,7 +127,7 @@ pub trait AsyncResultExt<T, E> {
     ///
     /// If the result is `Ok`, applies async function `op`.
     /// If `Err`, returns the provided `default` value.
-    fn async_map_or<U, F, Fut>(self, default: U, op: F) -> impl Future<Output = U>
+    fn async_map_or<U, F, Fut>(self, default: U, op: F) -> AsyncMapOr<T, E, U, F, Fut>
     where
         F: FnOnce(T) -> Fut,
         Fut: Future<Output = U>;
@@ -70,7 +136,11 @@ pub trait AsyncResultExt<T, E> {
     ///
     /// If the result is `Ok`, applies async function `op`.
     /// If `Err`, computes an async fallback via `default`.
-    fn async_map_or_else<U, D, F, Fut, DefFut>(self, default: D, op: F) -> impl Future<Output = U>
+    fn async_map_or_else<U, D, F, Fut, DefFut>(
+        self,
+        default: D,
+        op: F,
+    ) -> AsyncMapOrElse<T, E, D, F, Fut, DefFut>
     where
         D: FnOnce(E) -> DefFut,
         F: FnOnce(T) -> Fut,
@@ -80,7 +150,7 @@ pub trait AsyncResultExt<T, E> {
     /// Asynchronous version of [`Result::map_err`].
     ///
     /// Transforms the error using an async function `op`.
-    fn async_map_err<F, Fut, O>(self, op: F) -> impl Future<Output = Result<T, O>>
+    fn async_map_err<F, Fut, O>(self, op: F) -> AsyncMapErr<T, E, F, Fut>
     where
         F: FnOnce(E) -> Fut,
         Fut: Future<Output = O>;
@@ -88,7 +158,7 @@ pub trait AsyncResultExt<T, E> {
     /// Asynchronous version of [`Result::inspect`].
     ///
     /// Lets you asynchronously “peek” into the `Ok` value without modifying it.
-    fn async_inspect<F, Fut>(self, op: F) -> impl Future<Output = Self>
+    fn async_inspect<F, Fut>(self, op: F) -> AsyncInspect<T, E, F, Fut>
     where
         F: FnOnce(&T) -> Fut,
         Fut: Future<Output = ()>;
@@ -96,7 +166,7 @@ pub trait AsyncResultExt<T, E> {
     /// Asynchronous version of [`Result::inspect_err`].
     ///
     /// Lets you asynchronously “peek” into the `Err` value without modifying it.
-    fn async_inspect_err<F, Fut>(self, op: F) -> impl Future<Output = Self>
+    fn async_inspect_err<F, Fut>(self, op: F) -> AsyncInspectErr<T, E, F, Fut>
     where
         F: FnOnce(&E) -> Fut,
         Fut: Future<Output = ()>;
@@ -109,8 +179,7 @@ pub trait AsyncResultExt<T, E> {
     /// ```
     /// use async_result_ext::AsyncResultExt;
     ///
-    /// # #[tokio::main]
-    /// # async fn main() {
+    /// # futures::executor::block_on(async {
     /// let r: Result<i32, &str> = Ok(10);
     /// let is_even = r.async_is_ok_and(|v| async move { v % 2 == 0 }).await;
     /// assert!(is_even);
@@ -122,9 +191,9 @@ pub trait AsyncResultExt<T, E> {
     /// let r: Result<i32, &str> = Err("error");
     /// let is_even = r.async_is_ok_and(|v| async move { v % 2 == 0 }).await;
     /// assert!(!is_even);
-    /// # }
+    /// # });
     /// ```
-    fn async_is_ok_and<F, Fut>(self, op: F) -> impl Future<Output = bool>
+    fn async_is_ok_and<F, Fut>(self, op: F) -> AsyncIsOkAnd<T, E, F, Fut>
     where
         F: FnOnce(T) -> Fut,
         Fut: Future<Output = bool>;
@@ -137,8 +206,7 @@ pub trait AsyncResultExt<T, E> {
     /// ```
     /// use async_result_ext::AsyncResultExt;
     ///
-    /// # #[tokio::main]
-    /// # async fn main() {
+    /// # futures::executor::block_on(async {
     /// let r: Result<i32, &str> = Err("oops");
     /// let is_long = r.async_is_err_and(|e| async move { e.len() > 3 }).await;
     /// assert!(is_long);
@@ -150,223 +218,3341 @@ pub trait AsyncResultExt<T, E> {
     /// let r: Result<i32, &str> = Ok(42);
     /// let is_long = r.async_is_err_and(|e| async move { e.len() > 3 }).await;
     /// assert!(!is_long);
-    /// # }
+    /// # });
     /// ```
-    fn async_is_err_and<F, Fut>(self, op: F) -> impl Future<Output = bool>
+    fn async_is_err_and<F, Fut>(self, op: F) -> AsyncIsErrAnd<T, E, F, Fut>
     where
         F: FnOnce(E) -> Fut,
         Fut: Future<Output = bool>;
-}
 
-impl<T, E> AsyncResultExt<T, E> for Result<T, E> {
-    async fn async_map<U, F, Fut>(self, op: F) -> Result<U, E>
+    /// Asynchronous version of [`Result::unwrap_or_else`].
+    ///
+    /// Returns the `Ok` value, or computes a fallback from the error via async function `op`.
+    ///
+    /// ```
+    /// use async_result_ext::AsyncResultExt;
+    ///
+    /// # futures::executor::block_on(async {
+    /// let r: Result<i32, &str> = Err("missing");
+    /// let res = r.async_unwrap_or_else(|e| async move { e.len() as i32 }).await;
+    /// assert_eq!(res, 7);
+    /// # });
+    /// ```
+    fn async_unwrap_or_else<F, Fut>(self, op: F) -> AsyncUnwrapOrElse<T, E, F, Fut>
     where
-        F: FnOnce(T) -> Fut,
-        Fut: Future<Output = U>,
-    {
-        match self {
-            Ok(value) => Ok(op(value).await),
-            Err(err) => Err(err),
-        }
-    }
+        F: FnOnce(E) -> Fut,
+        Fut: Future<Output = T>;
 
-    async fn async_and_then<U, F, Fut>(self, op: F) -> Result<U, E>
+    /// Asynchronous version of [`Result::or_else`].
+    ///
+    /// If the result is `Err`, recovers by awaiting `op`, which returns a new `Result`.
+    /// If `Ok`, the value is passed through unchanged.
+    ///
+    /// ```
+    /// use async_result_ext::AsyncResultExt;
+    ///
+    /// # futures::executor::block_on(async {
+    /// let r: Result<i32, &str> = Err("retry");
+    /// let res: Result<i32, String> = r.async_or_else(|e| async move { Ok(e.len() as i32) }).await;
+    /// assert_eq!(res, Ok(5));
+    /// # });
+    /// ```
+    fn async_or_else<O, F, Fut>(self, op: F) -> AsyncOrElse<T, E, F, Fut>
     where
-        F: FnOnce(T) -> Fut,
-        Fut: Future<Output = Result<U, E>>,
-    {
-        match self {
-            Ok(value) => op(value).await,
-            Err(err) => Err(err),
-        }
-    }
+        F: FnOnce(E) -> Fut,
+        Fut: Future<Output = Result<T, O>>;
 
-    async fn async_map_or<U, F, Fut>(self, default: U, op: F) -> U
+    /// Like [`async_and_then`](AsyncResultExt::async_and_then), but lets `op` use a different
+    /// error type.
+    ///
+    /// If the result is `Err`, the error is converted via `O::from`, mirroring how `?` works.
+    ///
+    /// ```
+    /// use async_result_ext::AsyncResultExt;
+    ///
+    /// # futures::executor::block_on(async {
+    /// let r: Result<&str, std::num::ParseIntError> = Ok("2");
+    /// let res: Result<i32, Box<dyn std::error::Error>> = r
+    ///     .async_and_then_into(|v| async move { Ok(v.parse::<i32>()? * 2) })
+    ///     .await;
+    /// assert_eq!(res.unwrap(), 4);
+    /// # });
+    /// ```
+    fn async_and_then_into<U, O, F, Fut>(self, op: F) -> AsyncAndThenInto<T, E, F, Fut>
     where
+        O: From<E>,
         F: FnOnce(T) -> Fut,
-        Fut: Future<Output = U>,
-    {
-        match self {
-            Ok(value) => op(value).await,
-            Err(_) => default,
-        }
-    }
-    async fn async_map_or_else<U, D, F, Fut, DefFut>(self, default: D, op: F) -> U
+        Fut: Future<Output = Result<U, O>>;
+
+    /// Like [`async_map_err`](AsyncResultExt::async_map_err), but lifts the mapped error into `O`.
+    ///
+    /// The error is transformed by async function `op` and then converted via `O::from`.
+    /// If the result is `Ok`, `op` is never called.
+    fn async_map_err_into<O, F, Fut, M>(self, op: F) -> AsyncMapErrInto<T, E, O, F, Fut>
     where
-        D: FnOnce(E) -> DefFut,
-        F: FnOnce(T) -> Fut,
-        DefFut: Future<Output = U>,
-        Fut: Future<Output = U>,
-    {
-        match self {
-            Ok(value) => op(value).await,
-            Err(err) => default(err).await,
-        }
-    }
+        O: From<M>,
+        F: FnOnce(E) -> Fut,
+        Fut: Future<Output = M>;
+
+    /// Transforms both branches in a single call.
+    ///
+    /// Awaits `ok_op` on the `Ok` value or `err_op` on the `Err` value.
+    /// Only the closure matching the branch is ever called.
+    fn async_map_both<U, O, F, G, FFut, GFut>(
+        self,
+        ok_op: F,
+        err_op: G,
+    ) -> AsyncMapBoth<T, E, F, G, FFut, GFut>
+    where
+        F: FnOnce(T) -> FFut,
+        G: FnOnce(E) -> GFut,
+        FFut: Future<Output = U>,
+        GFut: Future<Output = O>;
 
-    async fn async_map_err<F, Fut, O>(self, op: F) -> Result<T, O>
+    /// Recovers from an error to a usable value.
+    ///
+    /// Returns the `Ok` value, or awaits `op` to compute a `T` from the error.
+    /// This is the same operation as [`async_unwrap_or_else`](AsyncResultExt::async_unwrap_or_else),
+    /// named for call sites where the intent is recovery rather than unwrapping.
+    ///
+    /// ```
+    /// use async_result_ext::AsyncResultExt;
+    ///
+    /// # futures::executor::block_on(async {
+    /// let r: Result<Vec<i32>, &str> = Err("cache miss");
+    /// let items = r.async_recover(|_| async move { vec![1, 2, 3] }).await;
+    /// assert_eq!(items, vec![1, 2, 3]);
+    /// # });
+    /// ```
+    fn async_recover<F, Fut>(self, op: F) -> AsyncUnwrapOrElse<T, E, F, Fut>
     where
         F: FnOnce(E) -> Fut,
-        Fut: Future<Output = O>,
-    {
-        match self {
-            Ok(value) => Ok(value),
-            Err(err) => Err(op(err).await),
-        }
-    }
+        Fut: Future<Output = T>;
 
-    async fn async_inspect<F, Fut>(self, op: F) -> Self
+    /// Asynchronous version of [`Result::and`].
+    ///
+    /// If the result is `Ok`, awaits `fut` and returns its result.
+    /// If `Err`, the error is returned and `fut` is dropped without ever being polled.
+    ///
+    /// ```
+    /// use async_result_ext::AsyncResultExt;
+    ///
+    /// # futures::executor::block_on(async {
+    /// let r: Result<i32, &str> = Ok(1);
+    /// let res = r.async_and(async { Ok::<_, &str>("next") }).await;
+    /// assert_eq!(res, Ok("next"));
+    /// # });
+    /// ```
+    fn async_and<U, Fut>(self, fut: Fut) -> AsyncAnd<T, E, Fut>
     where
-        F: FnOnce(&T) -> Fut,
-        Fut: Future<Output = ()>,
-    {
-        if let Ok(ref value) = self {
-            op(value).await;
-        }
-        self
-    }
+        Fut: Future<Output = Result<U, E>>;
 
-    async fn async_inspect_err<F, Fut>(self, op: F) -> Self
+    /// Asynchronous version of [`Result::or`].
+    ///
+    /// If the result is `Err`, awaits `fut` and returns its result.
+    /// If `Ok`, the value is returned and `fut` is dropped without ever being polled.
+    fn async_or<O, Fut>(self, fut: Fut) -> AsyncOr<T, E, Fut>
     where
-        F: FnOnce(&E) -> Fut,
-        Fut: Future<Output = ()>,
-    {
-        if let Err(ref err) = self {
-            op(err).await;
-        }
-        self
-    }
+        Fut: Future<Output = Result<T, O>>;
 
-    async fn async_is_ok_and<F, Fut>(self, op: F) -> bool
+    /// Speculative variant of [`async_map_or_else`](AsyncResultExt::async_map_or_else).
+    ///
+    /// The fallback future from `default` is started before the branch is inspected, so
+    /// it can make progress while the optimistic `op` runs on the `Ok` value. Because it
+    /// starts speculatively, `default` does not receive the error.
+    ///
+    /// If the result is `Ok`, both futures are polled together until `op` completes, and
+    /// the fallback is then dropped. If `Err`, the fallback is awaited and `op` is never called.
+    ///
+    /// # Cancellation
+    ///
+    /// When `op` wins, the fallback is dropped at whatever await point it reached, so it
+    /// must be cancellation-safe. Dropping the returned future drops both futures that
+    /// are still in flight.
+    fn async_map_or_else_concurrent<U, D, F, Fut, DefFut>(
+        self,
+        default: D,
+        op: F,
+    ) -> AsyncMapOrElseConcurrent<T, E, D, F, Fut, DefFut>
     where
+        D: FnOnce() -> DefFut,
         F: FnOnce(T) -> Fut,
-        Fut: Future<Output = bool>,
-    {
-        {
-            match self {
-                Err(_) => false,
-                Ok(v) => op(v).await,
-            }
-        }
-    }
+        DefFut: Future<Output = U>,
+        Fut: Future<Output = U>;
 
-    async fn async_is_err_and<F, Fut>(self, op: F) -> bool
+    /// Fallible variant of [`async_map_both`](AsyncResultExt::async_map_both).
+    ///
+    /// Awaits `ok_op` on the `Ok` value or `err_op` on the `Err` value; only the matching
+    /// closure runs. `err_op` produces the transformed error. If the closure that ran
+    /// fails, its error `M` is converted via `O::from` and becomes the output error.
+    ///
+    /// ```
+    /// use async_result_ext::AsyncResultExt;
+    ///
+    /// # futures::executor::block_on(async {
+    /// let r: Result<&str, i32> = Ok("12");
+    /// let res: Result<i32, String> = r
+    ///     .async_try_map_both(
+    ///         |v| async move { v.parse::<i32>().map_err(|e| e.to_string()) },
+    ///         |code| async move { Ok(format!("code {code}")) },
+    ///     )
+    ///     .await;
+    /// assert_eq!(res, Ok(12));
+    /// # });
+    /// ```
+    fn async_try_map_both<U, O, M, F, G, FFut, GFut>(
+        self,
+        ok_op: F,
+        err_op: G,
+    ) -> AsyncTryMapBoth<T, E, F, G, FFut, GFut>
     where
-        F: FnOnce(E) -> Fut,
-        Fut: Future<Output = bool>,
-    {
-        {
-            match self {
-                Ok(_) => false,
-                Err(e) => op(e).await,
-            }
-        }
-    }
-}
+        O: From<M>,
+        F: FnOnce(T) -> FFut,
+        G: FnOnce(E) -> GFut,
+        FFut: Future<Output = Result<U, M>>,
+        GFut: Future<Output = Result<O, M>>;
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[tokio::test]
-    async fn test_async_map() {
-        let r: Result<i32, &str> = Ok(2);
-        let res = r.async_map(|v| async move { v * 3 }).await;
-        assert_eq!(res, Ok(6));
+    /// Like [`async_inspect`](AsyncResultExt::async_inspect), but the inspection itself may fail.
+    ///
+    /// Awaits `op` with a reference to the `Ok` value. If `op` returns `Err`,
+    /// that error replaces the result; otherwise the `Ok` value is passed through.
+    /// If the result is `Err`, `op` is never called.
+    ///
+    /// ```
+    /// use async_result_ext::AsyncResultExt;
+    ///
+    /// # futures::executor::block_on(async {
+    /// let r: Result<i32, &str> = Ok(-1);
+    /// let res = r
+    ///     .async_inspect_try(|v| {
+    ///         let valid = *v >= 0;
+    ///         async move { if valid { Ok(()) } else { Err("audit failed") } }
+    ///     })
+    ///     .await;
+    /// assert_eq!(res, Err("audit failed"));
+    /// # });
+    /// ```
+    fn async_inspect_try<F, Fut>(self, op: F) -> AsyncInspectTry<T, E, F, Fut>
+    where
+        F: FnOnce(&T) -> Fut,
+        Fut: Future<Output = Result<(), E>>;
 
-        let r: Result<i32, &str> = Err("error");
-        let res = r.async_map(|v| async move { v * 3 }).await;
-        assert_eq!(res, Err("error"));
-    }
+    /// Awaits `op` with a reference to the whole result, then returns it unchanged.
+    ///
+    /// Unlike [`async_inspect`](AsyncResultExt::async_inspect) and
+    /// [`async_inspect_err`](AsyncResultExt::async_inspect_err), `op` runs for
+    /// both `Ok` and `Err`, which suits logging either outcome in one place.
+    ///
+    /// ```
+    /// use async_result_ext::AsyncResultExt;
+    ///
+    /// # futures::executor::block_on(async {
+    /// let r: Result<i32, &str> = Err("fail");
+    /// let res = r
+    ///     .async_tap(|r| {
+    ///         let outcome = if r.is_ok() { "ok" } else { "err" };
+    ///         async move { println!("finished: {outcome}") }
+    ///     })
+    ///     .await;
+    /// assert_eq!(res, Err("fail"));
+    /// # });
+    /// ```
+    fn async_tap<F, Fut>(self, op: F) -> AsyncTap<T, E, F, Fut>
+    where
+        F: FnOnce(&Result<T, E>) -> Fut,
+        Fut: Future<Output = ()>;
 
-    #[tokio::test]
-    async fn test_async_and_then() {
-        let r: Result<i32, &str> = Ok(2);
-        let res = r.async_and_then(|v| async move { Ok(v * 5) }).await;
-        assert_eq!(res, Ok(10));
+    /// Runs an async step on the `Ok` value that may decide there is nothing to produce.
+    ///
+    /// Awaits `op`, which returns `Ok(Some(_))`, `Ok(None)` or `Err`, and yields that
+    /// as the new result. If the result is `Err`, `op` is never called.
+    ///
+    /// ```
+    /// use async_result_ext::AsyncResultExt;
+    ///
+    /// # futures::executor::block_on(async {
+    /// let r: Result<i32, &str> = Ok(-3);
+    /// let res = r
+    ///     .async_filter_map(|v| async move { Ok((v > 0).then_some(v * 2)) })
+    ///     .await;
+    /// assert_eq!(res, Ok(None));
+    /// # });
+    /// ```
+    fn async_filter_map<U, F, Fut>(self, op: F) -> AsyncFilterMap<T, E, F, Fut>
+    where
+        F: FnOnce(T) -> Fut,
+        Fut: Future<Output = Result<Option<U>, E>>;
 
-        let r: Result<i32, &str> = Err("fail");
-        let res = r.async_and_then(|v| async move { Ok(v * 5) }).await;
-        assert_eq!(res, Err("fail"));
-    }
+    /// Asynchronous version of [`Result::map_or_default`] (unstable in `std`).
+    ///
+    /// Awaits `op` on the `Ok` value, or returns `U::default()` if the result is `Err`.
+    ///
+    /// ```
+    /// use async_result_ext::AsyncResultExt;
+    ///
+    /// # futures::executor::block_on(async {
+    /// let r: Result<i32, &str> = Err("fail");
+    /// let len = r.async_map_or_default(|v| async move { v * 2 }).await;
+    /// assert_eq!(len, 0);
+    ///
+    /// let r: Result<i32, &str> = Ok(7);
+    /// let label = r.async_map_or_default(|v| async move { format!("#{v}") }).await;
+    /// assert_eq!(label, "#7");
+    /// # });
+    /// ```
+    fn async_map_or_default<U, F, Fut>(self, op: F) -> AsyncMapOrDefault<T, E, F, Fut>
+    where
+        U: Default,
+        F: FnOnce(T) -> Fut,
+        Fut: Future<Output = U>;
 
-    #[tokio::test]
-    async fn test_async_map_or() {
-        let r: Result<i32, &str> = Ok(2);
-        let res = r.async_map_or(100, |v| async move { v * 4 }).await;
-        assert_eq!(res, 8);
+    /// Asynchronous version of [`Result::unwrap_or_default`].
+    ///
+    /// Returns the `Ok` value, or `T::default()` if the result is `Err`.
+    /// Completes immediately; see the trait docs.
+    ///
+    /// ```
+    /// use async_result_ext::AsyncResultExt;
+    ///
+    /// # futures::executor::block_on(async {
+    /// let r: Result<&str, &str> = Err("fail");
+    /// let res = r
+    ///     .async_map(|s| async move { s.to_uppercase() })
+    ///     .await
+    ///     .async_unwrap_or_default()
+    ///     .await;
+    /// assert_eq!(res, "");
+    /// # });
+    /// ```
+    fn async_unwrap_or_default(self) -> Ready<T>
+    where
+        T: Default;
 
-        let r: Result<i32, &str> = Err("fail");
-        let res = r.async_map_or(100, |v| async move { v * 4 }).await;
-        assert_eq!(res, 100);
-    }
+    /// Asynchronous version of [`Result::expect`].
+    ///
+    /// Returns the `Ok` value. On `Err`, awaits `msg` with a reference to the error
+    /// and panics with the message it produces, which can be a `String` or any
+    /// other [`Display`](fmt::Display) value.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the result is `Err`, once the message future has completed. The
+    /// panic unwinds out of the `poll` call that finished the message, like any other
+    /// panic inside a future.
+    ///
+    /// ```should_panic
+    /// use async_result_ext::AsyncResultExt;
+    ///
+    /// # futures::executor::block_on(async {
+    /// let r: Result<i32, &str> = Err("disk full");
+    /// r.async_expect(|e| {
+    ///     let e = e.to_string();
+    ///     async move { format!("failed to save: {e}") }
+    /// })
+    /// .await;
+    /// # });
+    /// ```
+    fn async_expect<M, F, Fut>(self, msg: F) -> AsyncExpect<T, E, F, Fut>
+    where
+        M: fmt::Display,
+        F: FnOnce(&E) -> Fut,
+        Fut: Future<Output = M>;
 
-    #[tokio::test]
-    async fn test_async_map_err() {
-        let r: Result<i32, &str> = Ok(10);
-        let res = r.async_map_err(|e| async move { e.len() }).await;
-        assert_eq!(res, Ok(10));
+    /// Returns `true` if the result is `Ok` with a value equal to the one `op` produces.
+    ///
+    /// The expected value is built asynchronously by `op`. If the result is `Err`,
+    /// returns `false` without calling `op`.
+    ///
+    /// ```
+    /// use async_result_ext::AsyncResultExt;
+    ///
+    /// # futures::executor::block_on(async {
+    /// let r: Result<i32, &str> = Ok(42);
+    /// assert!(r.async_contains(|| async { 40 + 2 }).await);
+    /// # });
+    /// ```
+    fn async_contains<F, Fut>(self, op: F) -> AsyncContains<T, E, F, Fut>
+    where
+        T: PartialEq,
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = T>;
 
-        let r: Result<i32, &str> = Err("fail");
-        let res = r.async_map_err(|e| async move { e.len() }).await;
-        assert_eq!(res, Err(4));
-    }
+    /// Returns `true` if the result is `Err` with an error equal to the one `op` produces.
+    ///
+    /// The expected error is built asynchronously by `op`. If the result is `Ok`,
+    /// returns `false` without calling `op`.
+    ///
+    /// ```
+    /// use async_result_ext::AsyncResultExt;
+    ///
+    /// # futures::executor::block_on(async {
+    /// let r: Result<i32, String> = Err("not found".to_string());
+    /// assert!(r.async_contains_err(|| async { "not found".to_string() }).await);
+    /// # });
+    /// ```
+    fn async_contains_err<F, Fut>(self, op: F) -> AsyncContainsErr<T, E, F, Fut>
+    where
+        E: PartialEq,
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = E>;
 
-    #[tokio::test]
-    async fn test_async_inspect_err() {
-        let r: Result<i32, &str> = Err("oops");
-        let mut seen = "";
-        let res = r
-            .async_inspect_err(|e| async {
-                seen = e;
-            })
-            .await;
-        assert_eq!(res, Err("oops"));
-        assert_eq!(seen, "oops");
+    /// Like [`async_map`](AsyncResultExt::async_map), but borrows a reusable `FnMut` closure.
+    ///
+    /// Use this when the same closure is applied to many results, e.g. inside a
+    /// retry loop or over a sequence, and may keep state between calls. Prefer
+    /// [`async_map`](AsyncResultExt::async_map) for one-shot closures.
+    ///
+    /// ```
+    /// use async_result_ext::AsyncResultExt;
+    ///
+    /// # futures::executor::block_on(async {
+    /// let mut calls = 0;
+    /// let mut count = |v: i32| {
+    ///     calls += 1;
+    ///     async move { v * 2 }
+    /// };
+    /// for r in [Ok(1), Err("fail"), Ok(3)] {
+    ///     let _ = r.async_map_mut(&mut count).await;
+    /// }
+    /// assert_eq!(calls, 2);
+    /// # });
+    /// ```
+    fn async_map_mut<U, F, Fut>(self, op: &mut F) -> AsyncMap<T, E, &mut F, Fut>
+    where
+        F: FnMut(T) -> Fut,
+        Fut: Future<Output = U>;
 
-        let r: Result<i32, &str> = Ok(10);
-        let res = r
-            .async_inspect_err(|e| async {
-                seen = e;
-            })
-            .await;
-        assert_eq!(res, Ok(10));
-    }
+    /// Like [`async_and_then`](AsyncResultExt::async_and_then), but borrows a reusable
+    /// `FnMut` closure.
+    ///
+    /// Use this when the same closure is applied to many results; prefer
+    /// [`async_and_then`](AsyncResultExt::async_and_then) for one-shot closures.
+    fn async_and_then_mut<U, F, Fut>(self, op: &mut F) -> AsyncAndThen<T, E, &mut F, Fut>
+    where
+        F: FnMut(T) -> Fut,
+        Fut: Future<Output = Result<U, E>>;
 
-    #[tokio::test]
-    async fn test_async_map_or_else() {
-        let r: Result<i32, &str> = Ok(3);
-        let res = r
-            .async_map_or_else(|e| async move { e.len() as i32 }, |v| async move { v * 2 })
-            .await;
-        assert_eq!(res, 6);
+    /// Like [`async_and_then`](AsyncResultExt::async_and_then), also passing a
+    /// borrowed context to `op`.
+    ///
+    /// Shared state such as a connection pool or configuration can be handed to each
+    /// step by reference instead of being cloned or moved into every closure. The
+    /// context is borrowed for as long as the returned future lives, so the future
+    /// built by `op` may hold on to it.
+    ///
+    /// ```
+    /// use async_result_ext::AsyncResultExt;
+    ///
+    /// struct Config {
+    ///     limit: u32,
+    /// }
+    ///
+    /// # futures::executor::block_on(async {
+    /// let config = Config { limit: 10 };
+    /// let r: Result<u32, &str> = Ok(4);
+    /// let checked = r
+    ///     .async_and_then_ctx(&config, |config, v| async move {
+    ///         if v <= config.limit { Ok(v * 2) } else { Err("over limit") }
+    ///     })
+    ///     .await;
+    /// assert_eq!(checked, Ok(8));
+    /// # });
+    /// ```
+    fn async_and_then_ctx<'c, C, U, F, Fut>(
+        self,
+        ctx: &'c C,
+        op: F,
+    ) -> AsyncAndThenCtx<'c, C, T, E, F, Fut>
+    where
+        C: ?Sized,
+        F: FnOnce(&'c C, T) -> Fut,
+        Fut: Future<Output = Result<U, E>>;
 
-        let r: Result<i32, &str> = Err("error");
-        let res = r
-            .async_map_or_else(|e| async move { e.len() as i32 }, |v| async move { v * 2 })
-            .await;
-        assert_eq!(res, 5);
-    }
+    /// Maps the `Ok` value with an async fallible `op`, flattening its `Result`.
+    ///
+    /// This is the same operation as [`async_and_then`](AsyncResultExt::async_and_then),
+    /// named for call sites that read as a map whose step can fail. Unlike
+    /// [`async_map`](AsyncResultExt::async_map) with the same closure, the result
+    /// is never nested.
+    ///
+    /// ```
+    /// use async_result_ext::AsyncResultExt;
+    ///
+    /// # futures::executor::block_on(async {
+    /// let r: Result<&str, std::num::ParseIntError> = Ok("7");
+    /// let parsed = r.async_map_then(|s| async move { s.parse::<i32>() }).await;
+    /// assert_eq!(parsed, Ok(7));
+    /// # });
+    /// ```
+    fn async_map_then<U, F, Fut>(self, op: F) -> AsyncAndThen<T, E, F, Fut>
+    where
+        F: FnOnce(T) -> Fut,
+        Fut: Future<Output = Result<U, E>>;
 
-    #[tokio::test]
-    async fn async_is_ok_and() {
-        let r: Result<i32, &str> = Ok(5);
-        let res = r.async_is_ok_and(|_| async move { false }).await;
-        assert!(!res);
+    /// Awaits `ok_op` on the `Ok` value or `err_op` on the `Err` value, keeping the
+    /// branches apart in an [`Either`](futures_util::future::Either).
+    ///
+    /// Unlike [`async_map_or_else`](AsyncResultExt::async_map_or_else), the two closures
+    /// may produce different types, and unlike [`async_map_both`](AsyncResultExt::async_map_both)
+    /// neither output is treated as an error. Requires the `futures` feature.
+    ///
+    /// ```
+    /// use async_result_ext::AsyncResultExt;
+    /// use futures::future::Either;
+    ///
+    /// # futures::executor::block_on(async {
+    /// let r: Result<u32, &str> = Err("bad request");
+    /// let routed = r
+    ///     .async_split(|id| async move { id * 2 }, |msg| async move { msg.len() })
+    ///     .await;
+    /// assert!(matches!(routed, Either::Right(11)));
+    /// # });
+    /// ```
+    #[cfg(feature = "futures")]
+    fn async_split<U, V, F, G, FFut, GFut>(
+        self,
+        ok_op: F,
+        err_op: G,
+    ) -> AsyncSplit<T, E, F, G, FFut, GFut>
+    where
+        F: FnOnce(T) -> FFut,
+        G: FnOnce(E) -> GFut,
+        FFut: Future<Output = U>,
+        GFut: Future<Output = V>;
 
-        let r: Result<i32, &str> = Err("error");
-        let res = r.async_is_ok_and(|_| async move { false }).await;
-        assert!(!res);
-    }
+    /// Converts the `Ok` value with an async fallible `op`, lifting the incoming error.
+    ///
+    /// This is the same operation as [`async_and_then_into`](AsyncResultExt::async_and_then_into),
+    /// named for call sites whose step is a conversion, like [`TryInto`]: a failed
+    /// conversion yields its own error `O`, and an incoming `Err` is converted via `O::from`.
+    ///
+    /// ```
+    /// use async_result_ext::AsyncResultExt;
+    ///
+    /// # futures::executor::block_on(async {
+    /// let r: Result<i64, std::num::TryFromIntError> = Ok(300);
+    /// let byte: Result<u8, Box<dyn std::error::Error>> =
+    ///     r.async_try_into(|v| async move { Ok(u8::try_from(v)?) }).await;
+    /// assert!(byte.is_err());
+    /// # });
+    /// ```
+    fn async_try_into<U, O, F, Fut>(self, op: F) -> AsyncAndThenInto<T, E, F, Fut>
+    where
+        O: From<E>,
+        F: FnOnce(T) -> Fut,
+        Fut: Future<Output = Result<U, O>>;
 
-    #[tokio::test]
-    async fn is_err_and() {
-        let r: Result<i32, &str> = Ok(5);
-        let res = r.async_is_err_and(|_| async move { true }).await;
-        assert!(!res);
+    /// Like [`async_map`](AsyncResultExt::async_map), also measuring how long `op` took.
+    ///
+    /// On `Ok`, `clock` is read right before `op` is called and again once its future
+    /// completes, and the difference is returned next to the mapped result. On `Err`,
+    /// nothing is measured and the duration is `None`.
+    ///
+    /// The clock is any `Fn() -> I` whose readings subtract to a [`Duration`], such as
+    /// `std::time::Instant::now` or `tokio::time::Instant::now`, so no particular
+    /// timer is assumed and a fake clock can be injected in tests.
+    ///
+    /// ```
+    /// use async_result_ext::AsyncResultExt;
+    /// use std::time::Instant;
+    ///
+    /// # futures::executor::block_on(async {
+    /// let r: Result<u32, &str> = Ok(3);
+    /// let (res, took) = r.async_map_timed(Instant::now, |v| async move { v + 1 }).await;
+    /// assert_eq!(res, Ok(4));
+    /// assert!(took.is_some());
+    /// # });
+    /// ```
+    fn async_map_timed<U, C, I, F, Fut>(self, clock: C, op: F) -> AsyncMapTimed<T, E, C, I, F, Fut>
+    where
+        C: Fn() -> I,
+        I: Sub<Output = Duration>,
+        F: FnOnce(T) -> Fut,
+        Fut: Future<Output = U>;
 
-        let r: Result<i32, &str> = Err("error");
-        let res = r.async_is_err_and(|_| async move { false }).await;
-        assert!(!res);
+    /// Asynchronously decides whether to recover from an error, without consuming it.
+    ///
+    /// On `Err`, awaits `op` with a reference to the error. If it returns `Some(value)`,
+    /// the result recovers to `Ok(value)`; if it returns `None`, the original error is
+    /// returned unchanged. `Ok` values pass through without calling `op`.
+    ///
+    /// This sits between [`async_inspect_err`](AsyncResultExt::async_inspect_err), which
+    /// can look at the error but never recovers, and
+    /// [`async_unwrap_or_else`](AsyncResultExt::async_unwrap_or_else), which always
+    /// recovers and consumes the error. `op` may also perform side effects, such as
+    /// logging, before deciding.
+    ///
+    /// ```
+    /// use async_result_ext::AsyncResultExt;
+    ///
+    /// # futures::executor::block_on(async {
+    /// let r: Result<u32, u16> = Err(404);
+    /// let res = r
+    ///     .async_or_default_with(|status| {
+    ///         let status = *status;
+    ///         async move { (status == 404).then_some(0) }
+    ///     })
+    ///     .await;
+    /// assert_eq!(res, Ok(0));
+    ///
+    /// let r: Result<u32, u16> = Err(500);
+    /// let res = r.async_or_default_with(|_| async { None }).await;
+    /// assert_eq!(res, Err(500));
+    /// # });
+    /// ```
+    fn async_or_default_with<F, Fut>(self, op: F) -> AsyncOrDefaultWith<T, E, F, Fut>
+    where
+        F: FnOnce(&E) -> Fut,
+        Fut: Future<Output = Option<T>>;
+
+    /// Replaces the error with one produced by `op`, discarding the original.
+    ///
+    /// On `Err`, the original error is dropped before `op` is called, and the error
+    /// awaited from `op` takes its place. On `Ok`, `op` is never called. Use
+    /// [`async_map_err`](AsyncResultExt::async_map_err) when the new error depends on
+    /// the old one.
+    ///
+    /// ```
+    /// use async_result_ext::AsyncResultExt;
+    ///
+    /// # futures::executor::block_on(async {
+    /// let r: Result<u32, &str> = Err("db: connection reset by peer");
+    /// let public = r.async_replace_err(|| async { "service unavailable" }).await;
+    /// assert_eq!(public, Err("service unavailable"));
+    /// # });
+    /// ```
+    fn async_replace_err<O, F, Fut>(self, op: F) -> AsyncReplaceErr<T, E, F, Fut>
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = O>;
+
+    /// Awaits `op` on the `Ok` value for its side effect, then yields `Ok(())`.
+    ///
+    /// Unlike [`async_inspect`](AsyncResultExt::async_inspect), `op` takes the value
+    /// by move and the value is not returned, which suits "do X, then signal
+    /// completion" steps. If the result is `Err`, it is returned unchanged.
+    ///
+    /// ```
+    /// use async_result_ext::AsyncResultExt;
+    ///
+    /// # futures::executor::block_on(async {
+    /// let mut sent = Vec::new();
+    /// let r: Result<String, &str> = Ok("hello".to_string());
+    /// let done = r
+    ///     .async_then_unit(|msg| {
+    ///         sent.push(msg);
+    ///         async {}
+    ///     })
+    ///     .await;
+    /// assert_eq!(done, Ok(()));
+    /// assert_eq!(sent, ["hello"]);
+    /// # });
+    /// ```
+    fn async_then_unit<F, Fut>(self, op: F) -> AsyncThenUnit<T, E, F, Fut>
+    where
+        F: FnOnce(T) -> Fut,
+        Fut: Future<Output = ()>;
+
+    /// Like [`async_and_then`](AsyncResultExt::async_and_then), but `op` borrows the
+    /// `Ok` value, which is returned next to the computed one.
+    ///
+    /// On success the output is `Ok((value, computed))`, so a later step can still use
+    /// the original value without cloning it. If `op` fails, the original value is
+    /// dropped and its error returned. If the result is `Err`, `op` is not called.
+    ///
+    /// ```
+    /// use async_result_ext::AsyncResultExt;
+    ///
+    /// # futures::executor::block_on(async {
+    /// let r: Result<String, &str> = Ok("payload".to_string());
+    /// let res = r
+    ///     .async_and_then_ref(|body| {
+    ///         let len = body.len();
+    ///         async move { if len > 0 { Ok(len) } else { Err("empty") } }
+    ///     })
+    ///     .await;
+    /// assert_eq!(res, Ok(("payload".to_string(), 7)));
+    /// # });
+    /// ```
+    fn async_and_then_ref<U, F, Fut>(self, op: F) -> AsyncAndThenRef<T, E, F, Fut>
+    where
+        F: FnOnce(&T) -> Fut,
+        Fut: Future<Output = Result<U, E>>;
+
+    /// Asynchronous version of [`Result::ok`].
+    ///
+    /// Converts the result into an `Option`, discarding the error. Completes
+    /// immediately; see the trait docs.
+    ///
+    /// ```
+    /// use async_result_ext::{AsyncOptionExt, AsyncResultExt};
+    ///
+    /// # futures::executor::block_on(async {
+    /// let r: Result<i32, &str> = Ok(4);
+    /// let res = r.async_ok().await.async_map(|v| async move { v + 1 }).await;
+    /// assert_eq!(res, Some(5));
+    /// # });
+    /// ```
+    fn async_ok(self) -> Ready<Option<T>>;
+
+    /// Asynchronous version of [`Result::err`].
+    ///
+    /// Converts the result into an `Option` of its error, discarding the `Ok`
+    /// value. Completes immediately; see the trait docs.
+    ///
+    /// ```
+    /// use async_result_ext::AsyncResultExt;
+    ///
+    /// # futures::executor::block_on(async {
+    /// let r: Result<i32, &str> = Err("fail");
+    /// assert_eq!(r.async_err().await, Some("fail"));
+    /// # });
+    /// ```
+    fn async_err(self) -> Ready<Option<E>>;
+
+    /// Like [`async_and_then`](AsyncResultExt::async_and_then), bounding `op` by a timeout.
+    ///
+    /// On `Ok`, the future built by `op` races the one returned by `sleep(dur)`, as in
+    /// [`timeout`](crate::timeout::timeout). If the sleep completes first, `op`'s future
+    /// is dropped and `on_timeout` produces the error. `sleep` is supplied by the
+    /// caller, e.g. `tokio::time::sleep`, so no runtime is assumed. If the result is
+    /// `Err`, nothing is called.
+    ///
+    /// # Cancellation
+    ///
+    /// On a timeout, `op`'s future is dropped at whatever await point it reached, so
+    /// it must be cancellation-safe. Dropping the returned future early drops both the
+    /// step future and the sleep future.
+    ///
+    /// ```
+    /// use core::time::Duration;
+    /// use async_result_ext::AsyncResultExt;
+    ///
+    /// # futures::executor::block_on(async {
+    /// let r: Result<u32, &str> = Ok(1);
+    /// let res = r
+    ///     .async_and_then_timeout(
+    ///         Duration::from_secs(1),
+    ///         |_| async {},
+    ///         || "lookup timed out",
+    ///         |_| core::future::pending::<Result<u32, &str>>(),
+    ///     )
+    ///     .await;
+    /// assert_eq!(res, Err("lookup timed out"));
+    /// # });
+    /// ```
+    fn async_and_then_timeout<U, S, D, F, Fut, SleepFut>(
+        self,
+        dur: Duration,
+        sleep: S,
+        on_timeout: D,
+        op: F,
+    ) -> AsyncAndThenTimeout<T, E, S, D, F, Fut, SleepFut>
+    where
+        S: FnOnce(Duration) -> SleepFut,
+        SleepFut: Future<Output = ()>,
+        D: FnOnce() -> E,
+        F: FnOnce(T) -> Fut,
+        Fut: Future<Output = Result<U, E>>;
+
+    /// Like [`async_or_else`](AsyncResultExt::async_or_else), but lifts the recovery
+    /// error into `O`.
+    ///
+    /// If the result is `Err`, awaits `op` with the error; if recovery fails too, its
+    /// error `M` is converted via `O::from`, mirroring how `?` works. The original
+    /// error is handed to `op`, so it only reaches the output if `op` passes it on.
+    /// If `Ok`, the value is passed through without calling `op`.
+    ///
+    /// ```
+    /// use async_result_ext::AsyncResultExt;
+    ///
+    /// # futures::executor::block_on(async {
+    /// let cached: Result<i32, &str> = Err("cache miss");
+    /// let res: Result<i32, Box<dyn std::error::Error>> = cached
+    ///     .async_or_else_into(|_| async { "x".parse::<i32>() })
+    ///     .await;
+    /// assert!(res.is_err());
+    /// # });
+    /// ```
+    fn async_or_else_into<O, F, Fut, M>(self, op: F) -> AsyncOrElseInto<T, E, O, F, Fut>
+    where
+        O: From<M>,
+        F: FnOnce(E) -> Fut,
+        Fut: Future<Output = Result<T, M>>;
+
+    /// Like [`async_and_then`](AsyncResultExt::async_and_then), but turns a panic
+    /// while polling `op`'s future into the error returned by `on_panic`.
+    ///
+    /// The panic payload is discarded. `Fut` must be
+    /// [`UnwindSafe`](std::panic::UnwindSafe), since state it captures may be
+    /// observed after the panic; wrap it in
+    /// [`AssertUnwindSafe`](std::panic::AssertUnwindSafe) when that is known to be
+    /// fine. Only panics raised while the future is polled are caught, not ones
+    /// from calling `op` itself, and only when panics unwind. Requires the
+    /// `futures` and `std` features.
+    ///
+    /// ```
+    /// use async_result_ext::AsyncResultExt;
+    ///
+    /// # futures::executor::block_on(async {
+    /// let r: Result<Vec<u8>, &str> = Ok(Vec::new());
+    /// let first = r
+    ///     .async_and_then_catch(|bytes| async move { Ok(bytes[0]) }, || "handler panicked")
+    ///     .await;
+    /// assert_eq!(first, Err("handler panicked"));
+    /// # });
+    /// ```
+    #[cfg(all(feature = "futures", feature = "std"))]
+    fn async_and_then_catch<U, F, Fut, D>(
+        self,
+        op: F,
+        on_panic: D,
+    ) -> AsyncAndThenCatch<T, E, F, Fut, D>
+    where
+        F: FnOnce(T) -> Fut,
+        Fut: Future<Output = Result<U, E>> + std::panic::UnwindSafe,
+        D: FnOnce() -> E;
+
+    /// Like [`async_map`](AsyncResultExt::async_map), but takes an async closure
+    /// through [`AsyncFnOnce`] instead of a closure returning a future.
+    ///
+    /// The returned future can't be named, since the future type of an
+    /// [`AsyncFnOnce`] isn't nameable on stable Rust.
+    ///
+    /// ```
+    /// use async_result_ext::AsyncResultExt;
+    ///
+    /// # futures::executor::block_on(async {
+    /// let r: Result<i32, &str> = Ok(21);
+    /// let doubled = r.async_map_async(async |v| v * 2).await;
+    /// assert_eq!(doubled, Ok(42));
+    /// # });
+    /// ```
+    fn async_map_async<U, F>(self, op: F) -> impl Future<Output = Result<U, E>>
+    where
+        F: AsyncFnOnce(T) -> U;
+
+    /// Maps the `Ok` value with `op`, or falls back to an async default that is started
+    /// up front.
+    ///
+    /// This is the same operation as
+    /// [`async_map_or_else_concurrent`](AsyncResultExt::async_map_or_else_concurrent),
+    /// named as the counterpart of [`async_map_or`](AsyncResultExt::async_map_or): the
+    /// default doesn't depend on the error, but is computed asynchronously rather than
+    /// passed in. The future from `default` is created and polled right away, alongside
+    /// `op`'s future if the result is `Ok`; the output of the matching branch is returned.
+    ///
+    /// # Cancellation
+    ///
+    /// On `Ok`, the default future loses and is dropped as soon as `op`'s future
+    /// completes, at whatever await point it reached, so it must be cancellation-safe.
+    ///
+    /// ```
+    /// use async_result_ext::AsyncResultExt;
+    ///
+    /// # futures::executor::block_on(async {
+    /// let r: Result<u32, &str> = Err("cache miss");
+    /// let port = r
+    ///     .async_map_or_async_default(|| async { 8080 }, |p| async move { p })
+    ///     .await;
+    /// assert_eq!(port, 8080);
+    /// # });
+    /// ```
+    fn async_map_or_async_default<U, D, F, Fut, DefFut>(
+        self,
+        default: D,
+        op: F,
+    ) -> AsyncMapOrElseConcurrent<T, E, D, F, Fut, DefFut>
+    where
+        D: FnOnce() -> DefFut,
+        F: FnOnce(T) -> Fut,
+        DefFut: Future<Output = U>,
+        Fut: Future<Output = U>;
+
+    /// Maps the `Err` value with `err_op`, or awaits `ok_inspect` with a reference to the
+    /// `Ok` value.
+    ///
+    /// This fuses [`async_map_err`](AsyncResultExt::async_map_err) and
+    /// [`async_inspect`](AsyncResultExt::async_inspect) into a single future, for the
+    /// common case of logging success while translating failures. Exactly one of
+    /// the closures is called.
+    ///
+    /// ```
+    /// use async_result_ext::AsyncResultExt;
+    ///
+    /// # futures::executor::block_on(async {
+    /// let r: Result<u32, std::num::ParseIntError> = "42".parse();
+    /// let res = r
+    ///     .async_map_err_inspect(
+    ///         |e| async move { format!("bad id: {e}") },
+    ///         |id| {
+    ///             let id = *id;
+    ///             async move { println!("loaded {id}") }
+    ///         },
+    ///     )
+    ///     .await;
+    /// assert_eq!(res, Ok(42));
+    /// # });
+    /// ```
+    fn async_map_err_inspect<O, F, G, FFut, GFut>(
+        self,
+        err_op: F,
+        ok_inspect: G,
+    ) -> AsyncMapErrInspect<T, E, F, G, FFut, GFut>
+    where
+        F: FnOnce(E) -> FFut,
+        G: FnOnce(&T) -> GFut,
+        FFut: Future<Output = O>,
+        GFut: Future<Output = ()>;
+
+    /// Like [`async_and_then`](AsyncResultExt::async_and_then), but calls `op` again on
+    /// failure, at most `attempts` times in total.
+    ///
+    /// Every `Err` from `op` counts as transient and triggers another attempt until
+    /// they run out; the last attempt's error is then returned. For selective or
+    /// delayed retries, use [`retry_if`](crate::retry::retry_if) or
+    /// [`retry_with_backoff`](crate::retry::retry_with_backoff) inside an
+    /// `async_and_then` step. Since each attempt consumes its input, the `Ok` value
+    /// is cloned for every attempt but the last, hence `T: Clone`. `attempts == 0`
+    /// behaves like `1`. An incoming `Err` is returned without calling `op`.
+    ///
+    /// # Cancellation
+    ///
+    /// Dropping the future drops the attempt in flight; no further attempts are made.
+    ///
+    /// ```
+    /// use async_result_ext::AsyncResultExt;
+    ///
+    /// # futures::executor::block_on(async {
+    /// let mut calls = 0;
+    /// let r: Result<&str, &str> = Ok("/health");
+    /// let res = r
+    ///     .async_and_then_retry(3, |path| {
+    ///         calls += 1;
+    ///         let attempt = calls;
+    ///         async move { if attempt < 3 { Err("503") } else { Ok(path.len()) } }
+    ///     })
+    ///     .await;
+    /// assert_eq!(res, Ok(7));
+    /// # });
+    /// ```
+    fn async_and_then_retry<U, F, Fut>(
+        self,
+        attempts: usize,
+        op: F,
+    ) -> AsyncAndThenRetry<T, E, F, Fut>
+    where
+        T: Clone,
+        F: FnMut(T) -> Fut,
+        Fut: Future<Output = Result<U, E>>;
+}
+
+impl<T, E> AsyncResultExt<T, E> for Result<T, E> {
+    fn async_map<U, F, Fut>(self, op: F) -> AsyncMap<T, E, F, Fut>
+    where
+        F: FnOnce(T) -> Fut,
+        Fut: Future<Output = U>,
+    {
+        AsyncMap::new(self, op)
+    }
+
+    fn async_and_then<U, F, Fut>(self, op: F) -> AsyncAndThen<T, E, F, Fut>
+    where
+        F: FnOnce(T) -> Fut,
+        Fut: Future<Output = Result<U, E>>,
+    {
+        AsyncAndThen::new(self, op)
+    }
+
+    fn async_map_or<U, F, Fut>(self, default: U, op: F) -> AsyncMapOr<T, E, U, F, Fut>
+    where
+        F: FnOnce(T) -> Fut,
+        Fut: Future<Output = U>,
+    {
+        AsyncMapOr::new(self, default, op)
+    }
+
+    fn async_map_or_else<U, D, F, Fut, DefFut>(
+        self,
+        default: D,
+        op: F,
+    ) -> AsyncMapOrElse<T, E, D, F, Fut, DefFut>
+    where
+        D: FnOnce(E) -> DefFut,
+        F: FnOnce(T) -> Fut,
+        DefFut: Future<Output = U>,
+        Fut: Future<Output = U>,
+    {
+        AsyncMapOrElse::new(self, default, op)
+    }
+
+    fn async_map_err<F, Fut, O>(self, op: F) -> AsyncMapErr<T, E, F, Fut>
+    where
+        F: FnOnce(E) -> Fut,
+        Fut: Future<Output = O>,
+    {
+        AsyncMapErr::new(self, op)
+    }
+
+    fn async_inspect<F, Fut>(self, op: F) -> AsyncInspect<T, E, F, Fut>
+    where
+        F: FnOnce(&T) -> Fut,
+        Fut: Future<Output = ()>,
+    {
+        AsyncInspect::new(self, op)
+    }
+
+    fn async_inspect_err<F, Fut>(self, op: F) -> AsyncInspectErr<T, E, F, Fut>
+    where
+        F: FnOnce(&E) -> Fut,
+        Fut: Future<Output = ()>,
+    {
+        AsyncInspectErr::new(self, op)
+    }
+
+    fn async_is_ok_and<F, Fut>(self, op: F) -> AsyncIsOkAnd<T, E, F, Fut>
+    where
+        F: FnOnce(T) -> Fut,
+        Fut: Future<Output = bool>,
+    {
+        AsyncIsOkAnd::new(self, op)
+    }
+
+    fn async_is_err_and<F, Fut>(self, op: F) -> AsyncIsErrAnd<T, E, F, Fut>
+    where
+        F: FnOnce(E) -> Fut,
+        Fut: Future<Output = bool>,
+    {
+        AsyncIsErrAnd::new(self, op)
+    }
+
+    fn async_unwrap_or_else<F, Fut>(self, op: F) -> AsyncUnwrapOrElse<T, E, F, Fut>
+    where
+        F: FnOnce(E) -> Fut,
+        Fut: Future<Output = T>,
+    {
+        AsyncUnwrapOrElse::new(self, op)
+    }
+
+    fn async_or_else<O, F, Fut>(self, op: F) -> AsyncOrElse<T, E, F, Fut>
+    where
+        F: FnOnce(E) -> Fut,
+        Fut: Future<Output = Result<T, O>>,
+    {
+        AsyncOrElse::new(self, op)
+    }
+
+    fn async_and_then_into<U, O, F, Fut>(self, op: F) -> AsyncAndThenInto<T, E, F, Fut>
+    where
+        O: From<E>,
+        F: FnOnce(T) -> Fut,
+        Fut: Future<Output = Result<U, O>>,
+    {
+        AsyncAndThenInto::new(self, op)
+    }
+
+    fn async_map_err_into<O, F, Fut, M>(self, op: F) -> AsyncMapErrInto<T, E, O, F, Fut>
+    where
+        O: From<M>,
+        F: FnOnce(E) -> Fut,
+        Fut: Future<Output = M>,
+    {
+        AsyncMapErrInto::new(self, op)
+    }
+
+    fn async_map_both<U, O, F, G, FFut, GFut>(
+        self,
+        ok_op: F,
+        err_op: G,
+    ) -> AsyncMapBoth<T, E, F, G, FFut, GFut>
+    where
+        F: FnOnce(T) -> FFut,
+        G: FnOnce(E) -> GFut,
+        FFut: Future<Output = U>,
+        GFut: Future<Output = O>,
+    {
+        AsyncMapBoth::new(self, ok_op, err_op)
+    }
+
+    fn async_recover<F, Fut>(self, op: F) -> AsyncUnwrapOrElse<T, E, F, Fut>
+    where
+        F: FnOnce(E) -> Fut,
+        Fut: Future<Output = T>,
+    {
+        AsyncUnwrapOrElse::new(self, op)
+    }
+
+    fn async_and<U, Fut>(self, fut: Fut) -> AsyncAnd<T, E, Fut>
+    where
+        Fut: Future<Output = Result<U, E>>,
+    {
+        AsyncAnd::new(self, fut)
+    }
+
+    fn async_or<O, Fut>(self, fut: Fut) -> AsyncOr<T, E, Fut>
+    where
+        Fut: Future<Output = Result<T, O>>,
+    {
+        AsyncOr::new(self, fut)
+    }
+
+    fn async_map_or_else_concurrent<U, D, F, Fut, DefFut>(
+        self,
+        default: D,
+        op: F,
+    ) -> AsyncMapOrElseConcurrent<T, E, D, F, Fut, DefFut>
+    where
+        D: FnOnce() -> DefFut,
+        F: FnOnce(T) -> Fut,
+        DefFut: Future<Output = U>,
+        Fut: Future<Output = U>,
+    {
+        AsyncMapOrElseConcurrent::new(self, default, op)
+    }
+
+    fn async_try_map_both<U, O, M, F, G, FFut, GFut>(
+        self,
+        ok_op: F,
+        err_op: G,
+    ) -> AsyncTryMapBoth<T, E, F, G, FFut, GFut>
+    where
+        O: From<M>,
+        F: FnOnce(T) -> FFut,
+        G: FnOnce(E) -> GFut,
+        FFut: Future<Output = Result<U, M>>,
+        GFut: Future<Output = Result<O, M>>,
+    {
+        AsyncTryMapBoth::new(self, ok_op, err_op)
+    }
+
+    fn async_inspect_try<F, Fut>(self, op: F) -> AsyncInspectTry<T, E, F, Fut>
+    where
+        F: FnOnce(&T) -> Fut,
+        Fut: Future<Output = Result<(), E>>,
+    {
+        AsyncInspectTry::new(self, op)
+    }
+
+    fn async_tap<F, Fut>(self, op: F) -> AsyncTap<T, E, F, Fut>
+    where
+        F: FnOnce(&Result<T, E>) -> Fut,
+        Fut: Future<Output = ()>,
+    {
+        AsyncTap::new(self, op)
+    }
+
+    fn async_filter_map<U, F, Fut>(self, op: F) -> AsyncFilterMap<T, E, F, Fut>
+    where
+        F: FnOnce(T) -> Fut,
+        Fut: Future<Output = Result<Option<U>, E>>,
+    {
+        AsyncFilterMap::new(self, op)
+    }
+
+    fn async_map_or_default<U, F, Fut>(self, op: F) -> AsyncMapOrDefault<T, E, F, Fut>
+    where
+        U: Default,
+        F: FnOnce(T) -> Fut,
+        Fut: Future<Output = U>,
+    {
+        AsyncMapOrDefault::new(self, op)
+    }
+
+    fn async_unwrap_or_default(self) -> Ready<T>
+    where
+        T: Default,
+    {
+        ready(self.unwrap_or_default())
+    }
+
+    fn async_expect<M, F, Fut>(self, msg: F) -> AsyncExpect<T, E, F, Fut>
+    where
+        M: fmt::Display,
+        F: FnOnce(&E) -> Fut,
+        Fut: Future<Output = M>,
+    {
+        AsyncExpect::new(self, msg)
+    }
+
+    fn async_contains<F, Fut>(self, op: F) -> AsyncContains<T, E, F, Fut>
+    where
+        T: PartialEq,
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = T>,
+    {
+        AsyncContains::new(self, op)
+    }
+
+    fn async_contains_err<F, Fut>(self, op: F) -> AsyncContainsErr<T, E, F, Fut>
+    where
+        E: PartialEq,
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = E>,
+    {
+        AsyncContainsErr::new(self, op)
+    }
+
+    fn async_map_mut<U, F, Fut>(self, op: &mut F) -> AsyncMap<T, E, &mut F, Fut>
+    where
+        F: FnMut(T) -> Fut,
+        Fut: Future<Output = U>,
+    {
+        AsyncMap::new(self, op)
+    }
+
+    fn async_and_then_mut<U, F, Fut>(self, op: &mut F) -> AsyncAndThen<T, E, &mut F, Fut>
+    where
+        F: FnMut(T) -> Fut,
+        Fut: Future<Output = Result<U, E>>,
+    {
+        AsyncAndThen::new(self, op)
+    }
+
+    fn async_and_then_ctx<'c, C, U, F, Fut>(
+        self,
+        ctx: &'c C,
+        op: F,
+    ) -> AsyncAndThenCtx<'c, C, T, E, F, Fut>
+    where
+        C: ?Sized,
+        F: FnOnce(&'c C, T) -> Fut,
+        Fut: Future<Output = Result<U, E>>,
+    {
+        AsyncAndThenCtx::new(self, ctx, op)
+    }
+
+    fn async_map_then<U, F, Fut>(self, op: F) -> AsyncAndThen<T, E, F, Fut>
+    where
+        F: FnOnce(T) -> Fut,
+        Fut: Future<Output = Result<U, E>>,
+    {
+        AsyncAndThen::new(self, op)
+    }
+
+    #[cfg(feature = "futures")]
+    fn async_split<U, V, F, G, FFut, GFut>(
+        self,
+        ok_op: F,
+        err_op: G,
+    ) -> AsyncSplit<T, E, F, G, FFut, GFut>
+    where
+        F: FnOnce(T) -> FFut,
+        G: FnOnce(E) -> GFut,
+        FFut: Future<Output = U>,
+        GFut: Future<Output = V>,
+    {
+        AsyncSplit::new(self, ok_op, err_op)
+    }
+
+    fn async_try_into<U, O, F, Fut>(self, op: F) -> AsyncAndThenInto<T, E, F, Fut>
+    where
+        O: From<E>,
+        F: FnOnce(T) -> Fut,
+        Fut: Future<Output = Result<U, O>>,
+    {
+        AsyncAndThenInto::new(self, op)
+    }
+
+    fn async_map_timed<U, C, I, F, Fut>(self, clock: C, op: F) -> AsyncMapTimed<T, E, C, I, F, Fut>
+    where
+        C: Fn() -> I,
+        I: Sub<Output = Duration>,
+        F: FnOnce(T) -> Fut,
+        Fut: Future<Output = U>,
+    {
+        AsyncMapTimed::new(self, clock, op)
+    }
+
+    fn async_or_default_with<F, Fut>(self, op: F) -> AsyncOrDefaultWith<T, E, F, Fut>
+    where
+        F: FnOnce(&E) -> Fut,
+        Fut: Future<Output = Option<T>>,
+    {
+        AsyncOrDefaultWith::new(self, op)
+    }
+
+    fn async_replace_err<O, F, Fut>(self, op: F) -> AsyncReplaceErr<T, E, F, Fut>
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = O>,
+    {
+        AsyncReplaceErr::new(self, op)
+    }
+
+    fn async_then_unit<F, Fut>(self, op: F) -> AsyncThenUnit<T, E, F, Fut>
+    where
+        F: FnOnce(T) -> Fut,
+        Fut: Future<Output = ()>,
+    {
+        AsyncThenUnit::new(self, op)
+    }
+
+    fn async_and_then_ref<U, F, Fut>(self, op: F) -> AsyncAndThenRef<T, E, F, Fut>
+    where
+        F: FnOnce(&T) -> Fut,
+        Fut: Future<Output = Result<U, E>>,
+    {
+        AsyncAndThenRef::new(self, op)
+    }
+
+    fn async_ok(self) -> Ready<Option<T>> {
+        ready(self.ok())
+    }
+
+    fn async_err(self) -> Ready<Option<E>> {
+        ready(self.err())
+    }
+
+    fn async_and_then_timeout<U, S, D, F, Fut, SleepFut>(
+        self,
+        dur: Duration,
+        sleep: S,
+        on_timeout: D,
+        op: F,
+    ) -> AsyncAndThenTimeout<T, E, S, D, F, Fut, SleepFut>
+    where
+        S: FnOnce(Duration) -> SleepFut,
+        SleepFut: Future<Output = ()>,
+        D: FnOnce() -> E,
+        F: FnOnce(T) -> Fut,
+        Fut: Future<Output = Result<U, E>>,
+    {
+        AsyncAndThenTimeout::new(self, dur, sleep, on_timeout, op)
+    }
+
+    fn async_or_else_into<O, F, Fut, M>(self, op: F) -> AsyncOrElseInto<T, E, O, F, Fut>
+    where
+        O: From<M>,
+        F: FnOnce(E) -> Fut,
+        Fut: Future<Output = Result<T, M>>,
+    {
+        AsyncOrElseInto::new(self, op)
+    }
+
+    #[cfg(all(feature = "futures", feature = "std"))]
+    fn async_and_then_catch<U, F, Fut, D>(
+        self,
+        op: F,
+        on_panic: D,
+    ) -> AsyncAndThenCatch<T, E, F, Fut, D>
+    where
+        F: FnOnce(T) -> Fut,
+        Fut: Future<Output = Result<U, E>> + std::panic::UnwindSafe,
+        D: FnOnce() -> E,
+    {
+        AsyncAndThenCatch::new(self, op, on_panic)
+    }
+
+    async fn async_map_async<U, F>(self, op: F) -> Result<U, E>
+    where
+        F: AsyncFnOnce(T) -> U,
+    {
+        match self {
+            Ok(value) => Ok(op(value).await),
+            Err(err) => Err(err),
+        }
+    }
+
+    fn async_map_or_async_default<U, D, F, Fut, DefFut>(
+        self,
+        default: D,
+        op: F,
+    ) -> AsyncMapOrElseConcurrent<T, E, D, F, Fut, DefFut>
+    where
+        D: FnOnce() -> DefFut,
+        F: FnOnce(T) -> Fut,
+        DefFut: Future<Output = U>,
+        Fut: Future<Output = U>,
+    {
+        AsyncMapOrElseConcurrent::new(self, default, op)
+    }
+
+    fn async_map_err_inspect<O, F, G, FFut, GFut>(
+        self,
+        err_op: F,
+        ok_inspect: G,
+    ) -> AsyncMapErrInspect<T, E, F, G, FFut, GFut>
+    where
+        F: FnOnce(E) -> FFut,
+        G: FnOnce(&T) -> GFut,
+        FFut: Future<Output = O>,
+        GFut: Future<Output = ()>,
+    {
+        AsyncMapErrInspect::new(self, err_op, ok_inspect)
+    }
+
+    fn async_and_then_retry<U, F, Fut>(
+        self,
+        attempts: usize,
+        op: F,
+    ) -> AsyncAndThenRetry<T, E, F, Fut>
+    where
+        T: Clone,
+        F: FnMut(T) -> Fut,
+        Fut: Future<Output = Result<U, E>>,
+    {
+        AsyncAndThenRetry::new(self, attempts, op)
+    }
+}
+
+/// Asynchronous read-only extensions for a borrowed [`Result<T, E>`].
+///
+/// Implemented for `&Result<T, E>`, so the result can be inspected or tested
+/// without giving up ownership. Call the methods on a reference; on an owned
+/// result, the consuming [`AsyncResultExt`] methods take precedence.
+///
+/// ```
+/// use async_result_ext::AsyncResultRefExt;
+///
+/// # futures::executor::block_on(async {
+/// let r: Result<String, &str> = Ok("hello".to_string());
+/// let is_long = (&r).async_is_ok_and(|s| async move { s.len() > 3 }).await;
+/// assert!(is_long);
+/// assert_eq!(r.as_deref(), Ok("hello"));
+/// # });
+/// ```
+pub trait AsyncResultRefExt<'a, T, E> {
+    /// Asynchronous version of [`Result::inspect`] on a borrowed result.
+    ///
+    /// Awaits `op` with a reference to the `Ok` value, then returns the borrowed result.
+    fn async_inspect<F, Fut>(self, op: F) -> AsyncInspectRef<'a, T, E, F, Fut>
+    where
+        F: FnOnce(&'a T) -> Fut,
+        Fut: Future<Output = ()>;
+
+    /// Asynchronous version of [`Result::inspect_err`] on a borrowed result.
+    ///
+    /// Awaits `op` with a reference to the `Err` value, then returns the borrowed result.
+    fn async_inspect_err<F, Fut>(self, op: F) -> AsyncInspectErrRef<'a, T, E, F, Fut>
+    where
+        F: FnOnce(&'a E) -> Fut,
+        Fut: Future<Output = ()>;
+
+    /// Asynchronous version of [`Result::is_ok_and`] on a borrowed result.
+    ///
+    /// Awaits `op` with a reference to the `Ok` value. Returns `false` for `Err`.
+    fn async_is_ok_and<F, Fut>(self, op: F) -> AsyncIsOkAnd<&'a T, &'a E, F, Fut>
+    where
+        F: FnOnce(&'a T) -> Fut,
+        Fut: Future<Output = bool>;
+
+    /// Asynchronous version of [`Result::is_err_and`] on a borrowed result.
+    ///
+    /// Awaits `op` with a reference to the `Err` value. Returns `false` for `Ok`.
+    fn async_is_err_and<F, Fut>(self, op: F) -> AsyncIsErrAnd<&'a T, &'a E, F, Fut>
+    where
+        F: FnOnce(&'a E) -> Fut,
+        Fut: Future<Output = bool>;
+
+    /// Asynchronous version of [`Result::map`] on a borrowed result.
+    ///
+    /// Awaits `op` with a reference to the `Ok` value and wraps its output in a new,
+    /// owned result. An `Err` is cloned into the output, hence the `E: Clone` bound;
+    /// the clone is taken when the method is called, not when the future is polled.
+    ///
+    /// ```
+    /// use async_result_ext::AsyncResultRefExt;
+    ///
+    /// # futures::executor::block_on(async {
+    /// let cached: Result<String, &str> = Ok("config".to_string());
+    /// let len = cached.async_map_ref(|s| async move { s.len() }).await;
+    /// assert_eq!(len, Ok(6));
+    /// assert_eq!(cached.as_deref(), Ok("config"));
+    /// # });
+    /// ```
+    fn async_map_ref<U, F, Fut>(self, op: F) -> AsyncMap<&'a T, E, F, Fut>
+    where
+        E: Clone,
+        F: FnOnce(&'a T) -> Fut,
+        Fut: Future<Output = U>;
+
+    /// Asynchronous version of [`Result::map_or_else`] on a borrowed result.
+    ///
+    /// Awaits `op` with a reference to the `Ok` value, or `default` with a reference
+    /// to the `Err` value, so either branch can derive a value without consuming
+    /// the result.
+    ///
+    /// ```
+    /// use async_result_ext::AsyncResultRefExt;
+    ///
+    /// # futures::executor::block_on(async {
+    /// let fetched: Result<Vec<u8>, String> = Err("timeout".to_string());
+    /// let summary = fetched
+    ///     .async_map_or_else_ref(
+    ///         |e| async move { format!("failed: {e}") },
+    ///         |body| async move { format!("{} bytes", body.len()) },
+    ///     )
+    ///     .await;
+    /// assert_eq!(summary, "failed: timeout");
+    /// assert!(fetched.is_err());
+    /// # });
+    /// ```
+    fn async_map_or_else_ref<U, D, F, Fut, DefFut>(
+        self,
+        default: D,
+        op: F,
+    ) -> AsyncMapOrElse<&'a T, &'a E, D, F, Fut, DefFut>
+    where
+        D: FnOnce(&'a E) -> DefFut,
+        F: FnOnce(&'a T) -> Fut,
+        DefFut: Future<Output = U>,
+        Fut: Future<Output = U>;
+}
+
+impl<'a, T, E> AsyncResultRefExt<'a, T, E> for &'a Result<T, E> {
+    fn async_inspect<F, Fut>(self, op: F) -> AsyncInspectRef<'a, T, E, F, Fut>
+    where
+        F: FnOnce(&'a T) -> Fut,
+        Fut: Future<Output = ()>,
+    {
+        AsyncInspectRef::new(self, op)
+    }
+
+    fn async_inspect_err<F, Fut>(self, op: F) -> AsyncInspectErrRef<'a, T, E, F, Fut>
+    where
+        F: FnOnce(&'a E) -> Fut,
+        Fut: Future<Output = ()>,
+    {
+        AsyncInspectErrRef::new(self, op)
+    }
+
+    fn async_is_ok_and<F, Fut>(self, op: F) -> AsyncIsOkAnd<&'a T, &'a E, F, Fut>
+    where
+        F: FnOnce(&'a T) -> Fut,
+        Fut: Future<Output = bool>,
+    {
+        AsyncIsOkAnd::new(self.as_ref(), op)
+    }
+
+    fn async_is_err_and<F, Fut>(self, op: F) -> AsyncIsErrAnd<&'a T, &'a E, F, Fut>
+    where
+        F: FnOnce(&'a E) -> Fut,
+        Fut: Future<Output = bool>,
+    {
+        AsyncIsErrAnd::new(self.as_ref(), op)
+    }
+
+    fn async_map_ref<U, F, Fut>(self, op: F) -> AsyncMap<&'a T, E, F, Fut>
+    where
+        E: Clone,
+        F: FnOnce(&'a T) -> Fut,
+        Fut: Future<Output = U>,
+    {
+        AsyncMap::new(self.as_ref().map_err(E::clone), op)
+    }
+
+    fn async_map_or_else_ref<U, D, F, Fut, DefFut>(
+        self,
+        default: D,
+        op: F,
+    ) -> AsyncMapOrElse<&'a T, &'a E, D, F, Fut, DefFut>
+    where
+        D: FnOnce(&'a E) -> DefFut,
+        F: FnOnce(&'a T) -> Fut,
+        DefFut: Future<Output = U>,
+        Fut: Future<Output = U>,
+    {
+        AsyncMapOrElse::new(self.as_ref(), default, op)
+    }
+}
+
+/// Asynchronous flattening of nested values.
+///
+/// Implemented for `Result<Result<T, E>, E>` and `Option<Option<T>>`, mirroring
+/// [`Result::flatten`] and [`Option::flatten`].
+/// Completes immediately; see [`AsyncResultExt`](AsyncResultExt#methods-that-complete-immediately).
+///
+/// ```
+/// use async_result_ext::{AsyncFlattenExt, AsyncResultExt};
+///
+/// # futures::executor::block_on(async {
+/// let r: Result<i32, &str> = Ok(2);
+/// let res = r
+///     .async_map(|v| async move { if v > 0 { Ok(v) } else { Err("negative") } })
+///     .await
+///     .async_flatten()
+///     .await;
+/// assert_eq!(res, Ok(2));
+/// # });
+/// ```
+pub trait AsyncFlattenExt {
+    /// The flattened value.
+    type Output;
+
+    /// Removes one level of nesting.
+    fn async_flatten(self) -> Ready<Self::Output>;
+}
+
+impl<T, E> AsyncFlattenExt for Result<Result<T, E>, E> {
+    type Output = Result<T, E>;
+
+    fn async_flatten(self) -> Ready<Result<T, E>> {
+        ready(match self {
+            Ok(inner) => inner,
+            Err(err) => Err(err),
+        })
+    }
+}
+
+/// Asynchronous flattening of a nested error.
+///
+/// Implemented for `Result<T, Result<E1, E2>>`, as produced by layered parsing
+/// where an outer failure may itself have failed in two ways. The nesting is
+/// collapsed into `Result<T, E1>`:
+///
+/// - `Ok(value)` becomes `Ok(value)`,
+/// - `Err(Ok(err))` becomes `Err(err)`,
+/// - `Err(Err(err))` becomes `Err(op(err).await)`, converting the inner error.
+///
+/// `op` is only called in the last case. A single error type is kept, rather
+/// than `Result<Result<T, E1>, E2>`, so the output chains with the other
+/// combinators.
+///
+/// ```
+/// use async_result_ext::AsyncFlattenErrExt;
+///
+/// # futures::executor::block_on(async {
+/// let r: Result<u8, Result<String, std::io::Error>> =
+///     Err(Err(std::io::Error::other("disk unplugged")));
+/// let res = r
+///     .async_flatten_err(|io| async move { format!("io: {io}") })
+///     .await;
+/// assert_eq!(res, Err("io: disk unplugged".to_string()));
+/// # });
+/// ```
+pub trait AsyncFlattenErrExt<T, E1, E2> {
+    /// Collapses the nested error into `E1`, awaiting `op` to convert an `E2`.
+    fn async_flatten_err<F, Fut>(self, op: F) -> AsyncFlattenErr<T, E1, E2, F, Fut>
+    where
+        F: FnOnce(E2) -> Fut,
+        Fut: Future<Output = E1>;
+}
+
+impl<T, E1, E2> AsyncFlattenErrExt<T, E1, E2> for Result<T, Result<E1, E2>> {
+    fn async_flatten_err<F, Fut>(self, op: F) -> AsyncFlattenErr<T, E1, E2, F, Fut>
+    where
+        F: FnOnce(E2) -> Fut,
+        Fut: Future<Output = E1>,
+    {
+        AsyncFlattenErr::new(self, op)
+    }
+}
+
+/// Asynchronous cloning out of a result holding a reference.
+///
+/// Implemented for `Result<&T, E>`, generalizing [`Result::cloned`] and
+/// [`Result::copied`] to values whose duplication must be awaited, such as
+/// handles that reopen a connection.
+///
+/// ```
+/// use async_result_ext::AsyncClonedExt;
+///
+/// # futures::executor::block_on(async {
+/// #[derive(Debug, PartialEq)]
+/// struct Session(u32);
+///
+/// let current = Session(7);
+/// let r: Result<&Session, &str> = Ok(&current);
+/// let forked = r.async_cloned(|s| {
+///     let id = s.0;
+///     async move { Session(id + 1) }
+/// })
+/// .await;
+/// assert_eq!(forked, Ok(Session(8)));
+/// # });
+/// ```
+pub trait AsyncClonedExt<'a, T, E> {
+    /// Awaits `op` to duplicate the referenced `Ok` value; an `Err` passes through.
+    fn async_cloned<F, Fut>(self, op: F) -> AsyncMap<&'a T, E, F, Fut>
+    where
+        F: FnOnce(&'a T) -> Fut,
+        Fut: Future<Output = T>;
+
+    /// Copies the referenced `Ok` value, like [`Result::copied`].
+    ///
+    /// Completes immediately; see [`AsyncResultExt`](AsyncResultExt#methods-that-complete-immediately).
+    fn async_copied(self) -> Ready<Result<T, E>>
+    where
+        T: Copy;
+}
+
+impl<'a, T, E> AsyncClonedExt<'a, T, E> for Result<&'a T, E> {
+    fn async_cloned<F, Fut>(self, op: F) -> AsyncMap<&'a T, E, F, Fut>
+    where
+        F: FnOnce(&'a T) -> Fut,
+        Fut: Future<Output = T>,
+    {
+        AsyncMap::new(self, op)
+    }
+
+    fn async_copied(self) -> Ready<Result<T, E>>
+    where
+        T: Copy,
+    {
+        ready(self.copied())
+    }
+}
+
+/// Asynchronous transposition between `Result` and `Option`.
+///
+/// Implemented for `Result<Option<T>, E>`, mirroring [`Result::transpose`].
+/// Completes immediately, like [`AsyncFlattenExt`].
+pub trait AsyncTransposeExt {
+    /// The transposed value.
+    type Output;
+
+    /// Swaps the `Result` and `Option` layers.
+    fn async_transpose(self) -> Ready<Self::Output>;
+}
+
+impl<T, E> AsyncTransposeExt for Result<Option<T>, E> {
+    type Output = Option<Result<T, E>>;
+
+    fn async_transpose(self) -> Ready<Option<Result<T, E>>> {
+        ready(match self {
+            Ok(Some(value)) => Some(Ok(value)),
+            Ok(None) => None,
+            Err(err) => Some(Err(err)),
+        })
+    }
+}
+
+/// Asynchronous splitting of a result holding a pair.
+///
+/// Implemented for `Result<(A, B), E>`, in the spirit of [`Option::unzip`]. An
+/// `Err` is duplicated into both halves, which is why `E` must be [`Clone`].
+/// Completes immediately, like [`AsyncFlattenExt`].
+///
+/// ```
+/// use async_result_ext::{AsyncResultExt, AsyncUnzipExt};
+///
+/// # futures::executor::block_on(async {
+/// let r: Result<&str, &str> = Ok("ada:36");
+/// let (name, age) = r
+///     .async_map(|s| async move { s.split_once(':').unwrap() })
+///     .await
+///     .async_unzip()
+///     .await;
+/// assert_eq!((name, age), (Ok("ada"), Ok("36")));
+/// # });
+/// ```
+pub trait AsyncUnzipExt {
+    /// The pair of results.
+    type Output;
+
+    /// Splits the pair into two results, cloning the error on `Err`.
+    fn async_unzip(self) -> Ready<Self::Output>;
+}
+
+impl<A, B, E: Clone> AsyncUnzipExt for Result<(A, B), E> {
+    type Output = (Result<A, E>, Result<B, E>);
+
+    fn async_unzip(self) -> Ready<(Result<A, E>, Result<B, E>)> {
+        ready(match self {
+            Ok((a, b)) => (Ok(a), Ok(b)),
+            Err(err) => (Err(err.clone()), Err(err)),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::cell::Cell;
+    use core::pin::{Pin, pin};
+    use core::task::{Context, Poll, Waker};
+    use futures::executor::block_on;
+    use std::format;
+    use std::string::{String, ToString};
+    use std::vec;
+    use std::vec::Vec;
+
+    #[test]
+    fn test_async_map() {
+        block_on(async {
+            let r: Result<i32, &str> = Ok(2);
+            let res = r.async_map(|v| async move { v * 3 }).await;
+            assert_eq!(res, Ok(6));
+
+            let r: Result<i32, &str> = Err("error");
+            let res = r.async_map(|v| async move { v * 3 }).await;
+            assert_eq!(res, Err("error"));
+        });
+    }
+
+    #[test]
+    fn test_async_and_then() {
+        block_on(async {
+            let r: Result<i32, &str> = Ok(2);
+            let res = r.async_and_then(|v| async move { Ok(v * 5) }).await;
+            assert_eq!(res, Ok(10));
+
+            let r: Result<i32, &str> = Err("fail");
+            let res = r.async_and_then(|v| async move { Ok(v * 5) }).await;
+            assert_eq!(res, Err("fail"));
+        });
+    }
+
+    #[test]
+    fn test_async_map_or() {
+        block_on(async {
+            let r: Result<i32, &str> = Ok(2);
+            let res = r.async_map_or(100, |v| async move { v * 4 }).await;
+            assert_eq!(res, 8);
+
+            let r: Result<i32, &str> = Err("fail");
+            let res = r.async_map_or(100, |v| async move { v * 4 }).await;
+            assert_eq!(res, 100);
+        });
+    }
+
+    #[test]
+    fn test_async_map_err() {
+        block_on(async {
+            let r: Result<i32, &str> = Ok(10);
+            let res = r.async_map_err(|e| async move { e.len() }).await;
+            assert_eq!(res, Ok(10));
+
+            let r: Result<i32, &str> = Err("fail");
+            let res = r.async_map_err(|e| async move { e.len() }).await;
+            assert_eq!(res, Err(4));
+        });
+    }
+
+    #[test]
+    fn test_async_inspect_err() {
+        block_on(async {
+            let r: Result<i32, &str> = Err("oops");
+            let mut seen = "";
+            let res = r
+                .async_inspect_err(|e| async {
+                    seen = e;
+                })
+                .await;
+            assert_eq!(res, Err("oops"));
+            assert_eq!(seen, "oops");
+
+            let r: Result<i32, &str> = Ok(10);
+            let res = r
+                .async_inspect_err(|e| async {
+                    seen = e;
+                })
+                .await;
+            assert_eq!(res, Ok(10));
+        });
+    }
+
+    #[test]
+    fn test_async_map_or_else() {
+        block_on(async {
+            let r: Result<i32, &str> = Ok(3);
+            let res = r
+                .async_map_or_else(|e| async move { e.len() as i32 }, |v| async move { v * 2 })
+                .await;
+            assert_eq!(res, 6);
+
+            let r: Result<i32, &str> = Err("error");
+            let res = r
+                .async_map_or_else(|e| async move { e.len() as i32 }, |v| async move { v * 2 })
+                .await;
+            assert_eq!(res, 5);
+        });
+    }
+
+    #[test]
+    fn async_is_ok_and() {
+        block_on(async {
+            let r: Result<i32, &str> = Ok(5);
+            let res = r.async_is_ok_and(|_| async move { false }).await;
+            assert!(!res);
+
+            let r: Result<i32, &str> = Err("error");
+            let res = r.async_is_ok_and(|_| async move { false }).await;
+            assert!(!res);
+        });
+    }
+
+    #[test]
+    fn is_err_and() {
+        block_on(async {
+            let r: Result<i32, &str> = Ok(5);
+            let res = r.async_is_err_and(|_| async move { true }).await;
+            assert!(!res);
+
+            let r: Result<i32, &str> = Err("error");
+            let res = r.async_is_err_and(|_| async move { false }).await;
+            assert!(!res);
+
+            let r: Result<i32, &str> = Err("error");
+            let res = r.async_is_err_and(|e| async move { e == "error" }).await;
+            assert!(res);
+        });
+    }
+
+    #[test]
+    fn test_async_unwrap_or_else() {
+        block_on(async {
+            let r: Result<i32, &str> = Ok(3);
+            let res = r
+                .async_unwrap_or_else(|e| async move { e.len() as i32 })
+                .await;
+            assert_eq!(res, 3);
+
+            let r: Result<i32, &str> = Err("error");
+            let res = r
+                .async_unwrap_or_else(|e| async move { e.len() as i32 })
+                .await;
+            assert_eq!(res, 5);
+        });
+    }
+
+    #[test]
+    fn test_async_or_else() {
+        block_on(async {
+            let r: Result<i32, &str> = Err("fail");
+            let res: Result<i32, usize> =
+                r.async_or_else(|e| async move { Ok(e.len() as i32) }).await;
+            assert_eq!(res, Ok(4));
+
+            let r: Result<i32, &str> = Err("fail");
+            let res: Result<i32, usize> = r.async_or_else(|e| async move { Err(e.len()) }).await;
+            assert_eq!(res, Err(4));
+
+            let r: Result<i32, &str> = Ok(7);
+            let res: Result<i32, usize> = r.async_or_else(|e| async move { Err(e.len()) }).await;
+            assert_eq!(res, Ok(7));
+        });
+    }
+
+    #[test]
+    fn test_async_and_then_into() {
+        block_on(async {
+            #[derive(Debug, PartialEq)]
+            enum AppError {
+                Io(std::io::ErrorKind),
+                Invalid,
+            }
+
+            impl From<std::io::Error> for AppError {
+                fn from(err: std::io::Error) -> Self {
+                    AppError::Io(err.kind())
+                }
+            }
+
+            let r: Result<i32, std::io::Error> = Ok(2);
+            let res = r
+                .async_and_then_into(|v| async move { Ok::<_, AppError>(v * 2) })
+                .await;
+            assert_eq!(res, Ok(4));
+
+            let r: Result<i32, std::io::Error> = Ok(-1);
+            let res = r
+                .async_and_then_into(|_| async move { Err::<i32, _>(AppError::Invalid) })
+                .await;
+            assert_eq!(res, Err(AppError::Invalid));
+
+            let r: Result<i32, std::io::Error> = Err(std::io::ErrorKind::NotFound.into());
+            let res = r
+                .async_and_then_into(|v| async move { Ok::<_, AppError>(v * 2) })
+                .await;
+            assert_eq!(res, Err(AppError::Io(std::io::ErrorKind::NotFound)));
+        });
+    }
+
+    #[test]
+    fn test_async_map_err_into() {
+        block_on(async {
+            #[derive(Debug, PartialEq)]
+            struct Code(usize);
+
+            #[derive(Debug, PartialEq)]
+            enum AppError {
+                Code(usize),
+            }
+
+            impl From<Code> for AppError {
+                fn from(code: Code) -> Self {
+                    AppError::Code(code.0)
+                }
+            }
+
+            let r: Result<i32, &str> = Err("fail");
+            let res: Result<i32, AppError> =
+                r.async_map_err_into(|e| async move { Code(e.len()) }).await;
+            assert_eq!(res, Err(AppError::Code(4)));
+
+            let r: Result<i32, &str> = Ok(1);
+            let mut called = false;
+            let res: Result<i32, AppError> = r
+                .async_map_err_into(|e| {
+                    called = true;
+                    async move { Code(e.len()) }
+                })
+                .await;
+            assert_eq!(res, Ok(1));
+            assert!(!called);
+        });
+    }
+
+    #[test]
+    fn test_async_flatten() {
+        block_on(async {
+            let r: Result<Result<i32, &str>, &str> = Ok(Ok(1));
+            assert_eq!(r.async_flatten().await, Ok(1));
+
+            let r: Result<Result<i32, &str>, &str> = Ok(Err("inner"));
+            assert_eq!(r.async_flatten().await, Err("inner"));
+
+            let r: Result<Result<i32, &str>, &str> = Err("outer");
+            assert_eq!(r.async_flatten().await, Err("outer"));
+        });
+    }
+
+    #[test]
+    fn test_async_flatten_err() {
+        block_on(async {
+            let r: Result<i32, Result<&str, u16>> = Ok(1);
+            assert_eq!(r.async_flatten_err(|_| never_polled()).await, Ok(1));
+
+            let r: Result<i32, Result<&str, u16>> = Err(Ok("syntax"));
+            assert_eq!(r.async_flatten_err(|_| never_polled()).await, Err("syntax"));
+
+            let r: Result<i32, Result<String, u16>> = Err(Err(503));
+            let res = r
+                .async_flatten_err(|code| async move { format!("status {code}") })
+                .await;
+            assert_eq!(res, Err("status 503".to_string()));
+        });
+    }
+
+    #[test]
+    fn test_async_cloned() {
+        #[derive(Debug, PartialEq)]
+        struct Handle {
+            id: u32,
+            generation: u32,
+        }
+
+        impl Handle {
+            async fn duplicate(&self) -> Handle {
+                yield_now().await;
+                Handle {
+                    id: self.id,
+                    generation: self.generation + 1,
+                }
+            }
+        }
+
+        block_on(async {
+            let original = Handle {
+                id: 1,
+                generation: 0,
+            };
+            let r: Result<&Handle, &str> = Ok(&original);
+            let res = r.async_cloned(Handle::duplicate).await;
+            assert_eq!(
+                res,
+                Ok(Handle {
+                    id: 1,
+                    generation: 1
+                })
+            );
+            assert_eq!(original.generation, 0);
+
+            let r: Result<&Handle, &str> = Err("closed");
+            assert_eq!(r.async_cloned(|_| never_polled()).await, Err("closed"));
+        });
+    }
+
+    #[test]
+    fn test_async_copied() {
+        block_on(async {
+            let v = 3;
+            let r: Result<&i32, &str> = Ok(&v);
+            assert_eq!(r.async_copied().await, Ok(3));
+
+            let r: Result<&i32, &str> = Err("fail");
+            assert_eq!(r.async_copied().await, Err("fail"));
+        });
+    }
+
+    #[test]
+    fn test_async_transpose() {
+        block_on(async {
+            let r: Result<Option<i32>, &str> = Ok(Some(1));
+            assert_eq!(r.async_transpose().await, Some(Ok(1)));
+
+            let r: Result<Option<i32>, &str> = Ok(None);
+            assert_eq!(r.async_transpose().await, None);
+
+            let r: Result<Option<i32>, &str> = Err("error");
+            assert_eq!(r.async_transpose().await, Some(Err("error")));
+        });
+    }
+
+    #[test]
+    fn test_async_unzip() {
+        block_on(async {
+            let r: Result<(i32, String), &str> = Ok((1, "one".to_string()));
+            assert_eq!(r.async_unzip().await, (Ok(1), Ok("one".to_string())));
+
+            let r: Result<(i32, String), String> = Err("gone".to_string());
+            let (left, right) = r.async_unzip().await;
+            assert_eq!(left, Err("gone".to_string()));
+            assert_eq!(right, Err("gone".to_string()));
+        });
+    }
+
+    #[test]
+    fn test_async_map_both() {
+        block_on(async {
+            let mut ok_ran = false;
+            let mut err_ran = false;
+            let r: Result<i32, &str> = Ok(2);
+            let res = r
+                .async_map_both(
+                    |v| {
+                        ok_ran = true;
+                        async move { v * 2 }
+                    },
+                    |e| {
+                        err_ran = true;
+                        async move { e.len() }
+                    },
+                )
+                .await;
+            assert_eq!(res, Ok(4));
+            assert!(ok_ran && !err_ran);
+
+            let mut ok_ran = false;
+            let mut err_ran = false;
+            let r: Result<i32, &str> = Err("fail");
+            let res = r
+                .async_map_both(
+                    |v| {
+                        ok_ran = true;
+                        async move { v * 2 }
+                    },
+                    |e| {
+                        err_ran = true;
+                        async move { e.len() }
+                    },
+                )
+                .await;
+            assert_eq!(res, Err(4));
+            assert!(!ok_ran && err_ran);
+        });
+    }
+
+    #[test]
+    fn test_async_recover() {
+        block_on(async {
+            let r: Result<i32, &str> = Ok(3);
+            let res = r.async_recover(|_| async move { 0 }).await;
+            assert_eq!(res, 3);
+
+            let r: Result<i32, &str> = Err("error");
+            let res = r.async_recover(|e| async move { -(e.len() as i32) }).await;
+            assert_eq!(res, -5);
+        });
+    }
+
+    pub(crate) fn never_polled<T>() -> impl Future<Output = T> {
+        std::future::poll_fn(|_| panic!("future must not be polled"))
+    }
+
+    /// Yields `Pending` once before completing, forcing a second poll.
+    pub(crate) async fn yield_now() {
+        let mut yielded = false;
+        core::future::poll_fn(|cx| {
+            if yielded {
+                Poll::Ready(())
+            } else {
+                yielded = true;
+                cx.waker().wake_by_ref();
+                Poll::Pending
+            }
+        })
+        .await
+    }
+
+    /// Fake timer: completes after being polled `ticks` more times.
+    pub(crate) async fn after(ticks: usize) {
+        let mut remaining = ticks;
+        core::future::poll_fn(|cx| {
+            if remaining == 0 {
+                Poll::Ready(())
+            } else {
+                remaining -= 1;
+                cx.waker().wake_by_ref();
+                Poll::Pending
+            }
+        })
+        .await
+    }
+
+    /// Polls `fut` a single time with a no-op waker.
+    ///
+    /// Used to drive a future to an await point before dropping it.
+    pub(crate) fn poll_once<F: Future + ?Sized>(fut: Pin<&mut F>) -> Poll<F::Output> {
+        fut.poll(&mut Context::from_waker(Waker::noop()))
+    }
+
+    /// Records in the borrowed flag that it has been dropped.
+    pub(crate) struct DropFlag<'a>(pub(crate) &'a Cell<bool>);
+
+    impl Drop for DropFlag<'_> {
+        fn drop(&mut self) {
+            self.0.set(true);
+        }
+    }
+
+    #[test]
+    fn test_async_and() {
+        block_on(async {
+            let r: Result<i32, &str> = Ok(1);
+            let res = r.async_and(async { Ok::<_, &str>(2) }).await;
+            assert_eq!(res, Ok(2));
+
+            let r: Result<i32, &str> = Ok(1);
+            let res = r.async_and(async { Err::<i32, _>("second") }).await;
+            assert_eq!(res, Err("second"));
+
+            let r: Result<i32, &str> = Err("first");
+            let res = r.async_and(never_polled::<Result<i32, &str>>()).await;
+            assert_eq!(res, Err("first"));
+        });
+    }
+
+    #[test]
+    fn test_async_or() {
+        block_on(async {
+            let r: Result<i32, &str> = Err("first");
+            let res = r.async_or(async { Ok::<_, usize>(2) }).await;
+            assert_eq!(res, Ok(2));
+
+            let r: Result<i32, &str> = Err("first");
+            let res = r.async_or(async { Err::<i32, _>(0usize) }).await;
+            assert_eq!(res, Err(0));
+
+            let r: Result<i32, &str> = Ok(1);
+            let res = r.async_or(never_polled::<Result<i32, usize>>()).await;
+            assert_eq!(res, Ok(1));
+        });
+    }
+
+    #[test]
+    fn test_async_map_or_else_concurrent() {
+        block_on(async {
+            let polled = Cell::new(false);
+            let dropped = Cell::new(false);
+            let r: Result<i32, &str> = Ok(3);
+            let res = r
+                .async_map_or_else_concurrent(
+                    || {
+                        let guard = DropFlag(&dropped);
+                        let polled = &polled;
+                        core::future::poll_fn(move |_| {
+                            let _guard = &guard;
+                            polled.set(true);
+                            Poll::Pending
+                        })
+                    },
+                    |v| async move { v * 2 },
+                )
+                .await;
+            assert_eq!(res, 6);
+            assert!(polled.get());
+            assert!(dropped.get());
+
+            let r: Result<i32, &str> = Err("error");
+            let res = r
+                .async_map_or_else_concurrent(|| async { -1 }, |_| never_polled())
+                .await;
+            assert_eq!(res, -1);
+        });
+    }
+
+    #[test]
+    fn test_async_try_map_both() {
+        block_on(async {
+            #[derive(Debug, PartialEq)]
+            enum AppError {
+                Upstream(usize),
+                Transform(&'static str),
+            }
+
+            impl From<&'static str> for AppError {
+                fn from(msg: &'static str) -> Self {
+                    AppError::Transform(msg)
+                }
+            }
+
+            let r: Result<i32, &str> = Ok(2);
+            let res: Result<i32, AppError> = r
+                .async_try_map_both(
+                    |v| async move { Ok::<_, &str>(v * 2) },
+                    |e| async move { Ok(AppError::Upstream(e.len())) },
+                )
+                .await;
+            assert_eq!(res, Ok(4));
+
+            let r: Result<i32, &str> = Ok(-1);
+            let res: Result<i32, AppError> = r
+                .async_try_map_both(
+                    |_| async move { Err("negative") },
+                    |e| async move { Ok(AppError::Upstream(e.len())) },
+                )
+                .await;
+            assert_eq!(res, Err(AppError::Transform("negative")));
+
+            let r: Result<i32, &str> = Err("fail");
+            let res: Result<i32, AppError> = r
+                .async_try_map_both(
+                    |v| async move { Ok::<_, &str>(v * 2) },
+                    |e| async move { Ok(AppError::Upstream(e.len())) },
+                )
+                .await;
+            assert_eq!(res, Err(AppError::Upstream(4)));
+
+            let r: Result<i32, &str> = Err("fail");
+            let res: Result<i32, AppError> = r
+                .async_try_map_both(
+                    |v| async move { Ok(v * 2) },
+                    |_| async move { Err("unmappable") },
+                )
+                .await;
+            assert_eq!(res, Err(AppError::Transform("unmappable")));
+        });
+    }
+
+    #[test]
+    fn test_send_futures() {
+        fn assert_send<T: Send>(_: &T) {}
+
+        fn from_result<R: AsyncResultExt<i32, String>>(r: R) {
+            let fut = r.async_and_then(|v| async move { Ok(v + 1) });
+            assert_send(&fut);
+        }
+
+        fn from_option<O: AsyncOptionExt<i32>>(o: O) {
+            let fut = o.async_map(|v| async move { v + 1 });
+            assert_send(&fut);
+        }
+
+        from_result(Ok::<i32, String>(1));
+        from_option(Some(1));
+    }
+
+    #[test]
+    fn test_named_future_in_struct() {
+        block_on(async {
+            type Double = fn(i32) -> Ready<i32>;
+
+            struct Stage {
+                fut: result::AsyncMap<i32, &'static str, Double, Ready<i32>>,
+            }
+
+            let double: Double = |v| ready(v * 2);
+            let stage = Stage {
+                fut: Ok(2).async_map(double),
+            };
+            assert_eq!(stage.fut.await, Ok(4));
+        });
+    }
+
+    #[test]
+    fn test_async_inspect_try() {
+        block_on(async {
+            let mut audited = None;
+            let r: Result<i32, &str> = Ok(5);
+            let res = r
+                .async_inspect_try(|v| {
+                    audited = Some(*v);
+                    async { Ok(()) }
+                })
+                .await;
+            assert_eq!(res, Ok(5));
+            assert_eq!(audited, Some(5));
+
+            let r: Result<i32, &str> = Ok(5);
+            let res = r.async_inspect_try(|_| async { Err("audit failed") }).await;
+            assert_eq!(res, Err("audit failed"));
+
+            let r: Result<i32, &str> = Err("fail");
+            let res = r.async_inspect_try(|_| never_polled()).await;
+            assert_eq!(res, Err("fail"));
+        });
+    }
+
+    #[test]
+    fn test_async_result_ref_inspect() {
+        block_on(async {
+            let r: Result<String, String> = Ok("value".to_string());
+            let mut seen = None;
+            let res = (&r)
+                .async_inspect(|v| {
+                    seen = Some(v.clone());
+                    async {}
+                })
+                .await;
+            assert_eq!(res, &r);
+            assert_eq!(seen.as_deref(), Some("value"));
+            (&r).async_inspect_err(|_| never_polled()).await;
+
+            let r: Result<String, String> = Err("boom".to_string());
+            let mut seen = None;
+            (&r).async_inspect_err(|e| {
+                seen = Some(e.len());
+                async {}
+            })
+            .await;
+            assert_eq!(seen, Some(4));
+            (&r).async_inspect(|_| never_polled()).await;
+
+            // The original result is still owned and usable.
+            assert_eq!(r, Err("boom".to_string()));
+        });
+    }
+
+    #[test]
+    fn test_async_result_ref_is_ok_and_is_err_and() {
+        block_on(async {
+            let r: Result<String, String> = Ok("value".to_string());
+            assert!((&r).async_is_ok_and(|v| async move { v == "value" }).await);
+            assert!(!(&r).async_is_err_and(|_| never_polled()).await);
+
+            let r: Result<String, String> = Err("boom".to_string());
+            assert!(
+                (&r).async_is_err_and(|e| async move { e.starts_with('b') })
+                    .await
+            );
+            assert!(!(&r).async_is_ok_and(|_| never_polled()).await);
+
+            let owned = r.async_map_err(|e| async move { e.len() }).await;
+            assert_eq!(owned, Err(4));
+        });
+    }
+
+    #[test]
+    fn test_async_result_ref_map() {
+        block_on(async {
+            let r: Result<String, String> = Ok("value".to_string());
+            let res = r.async_map_ref(|v| async move { v.len() }).await;
+            assert_eq!(res, Ok(5));
+            let res = (&r)
+                .async_map_ref(|v| async move { v.to_uppercase() })
+                .await;
+            assert_eq!(res.as_deref(), Ok("VALUE"));
+
+            let r: Result<String, String> = Err("boom".to_string());
+            let res = r.async_map_ref(|_| never_polled::<usize>()).await;
+            assert_eq!(res, Err("boom".to_string()));
+            assert_eq!(r, Err("boom".to_string()));
+        });
+    }
+
+    #[test]
+    fn test_async_result_ref_map_or_else() {
+        block_on(async {
+            let r: Result<String, String> = Ok("value".to_string());
+            let len = r
+                .async_map_or_else_ref(|_| never_polled(), |v| async move { v.len() })
+                .await;
+            assert_eq!(len, 5);
+            assert_eq!(r.as_deref(), Ok("value"));
+
+            let r: Result<String, String> = Err("boom".to_string());
+            let len = (&r)
+                .async_map_or_else_ref(|e| async move { e.len() + 100 }, |_| never_polled())
+                .await;
+            assert_eq!(len, 104);
+            assert_eq!(r, Err("boom".to_string()));
+        });
+    }
+
+    #[test]
+    fn test_async_tap() {
+        block_on(async {
+            let mut seen = Vec::new();
+
+            let r: Result<i32, &str> = Ok(1);
+            let res = r
+                .async_tap(|r| {
+                    seen.push(r.is_ok());
+                    async {}
+                })
+                .await;
+            assert_eq!(res, Ok(1));
+
+            let r: Result<i32, &str> = Err("fail");
+            let res = r
+                .async_tap(|r| {
+                    seen.push(r.is_ok());
+                    async {}
+                })
+                .await;
+            assert_eq!(res, Err("fail"));
+
+            assert_eq!(seen, [true, false]);
+        });
+    }
+
+    #[test]
+    fn test_async_filter_map() {
+        block_on(async {
+            let enrich = |v: i32| async move {
+                match v {
+                    0 => Err("zero"),
+                    v if v > 0 => Ok(Some(v * 2)),
+                    _ => Ok(None),
+                }
+            };
+
+            let r: Result<i32, &str> = Ok(2);
+            assert_eq!(r.async_filter_map(enrich).await, Ok(Some(4)));
+
+            let r: Result<i32, &str> = Ok(-2);
+            assert_eq!(r.async_filter_map(enrich).await, Ok(None));
+
+            let r: Result<i32, &str> = Ok(0);
+            assert_eq!(r.async_filter_map(enrich).await, Err("zero"));
+
+            let r: Result<i32, &str> = Err("fail");
+            let res: Result<Option<i32>, &str> = r.async_filter_map(|_| never_polled()).await;
+            assert_eq!(res, Err("fail"));
+        });
+    }
+
+    #[test]
+    fn test_async_map_or_default() {
+        block_on(async {
+            let r: Result<i32, &str> = Ok(3);
+            assert_eq!(r.async_map_or_default(|v| async move { v * 4 }).await, 12);
+
+            let r: Result<i32, &str> = Err("fail");
+            let res: String = r.async_map_or_default(|_| never_polled()).await;
+            assert_eq!(res, "");
+        });
+    }
+
+    #[test]
+    fn test_async_unwrap_or_default() {
+        block_on(async {
+            let r: Result<i32, &str> = Ok(5);
+            assert_eq!(r.async_unwrap_or_default().await, 5);
+
+            let r: Result<i32, &str> = Err("fail");
+            assert_eq!(r.async_unwrap_or_default().await, 0);
+
+            let r: Result<Vec<u8>, &str> = Ok(vec![1, 2]);
+            assert_eq!(r.async_unwrap_or_default().await, [1, 2]);
+
+            let r: Result<Vec<u8>, &str> = Err("fail");
+            assert!(r.async_unwrap_or_default().await.is_empty());
+        });
+    }
+
+    #[test]
+    fn test_async_expect_ok() {
+        block_on(async {
+            let r: Result<i32, &str> = Ok(3);
+            assert_eq!(r.async_expect(|_| never_polled::<String>()).await, 3);
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "lookup failed for user 42: not found")]
+    fn test_async_expect_err() {
+        block_on(async {
+            let r: Result<i32, &str> = Err("not found");
+            r.async_expect(|e| {
+                let e = e.to_string();
+                async move { format!("lookup failed for user 42: {e}") }
+            })
+            .await;
+        });
+    }
+
+    #[test]
+    fn test_async_contains() {
+        block_on(async {
+            let r: Result<i32, &str> = Ok(3);
+            assert!(r.async_contains(|| async { 3 }).await);
+
+            let r: Result<i32, &str> = Ok(3);
+            assert!(!r.async_contains(|| async { 4 }).await);
+
+            let r: Result<i32, &str> = Err("fail");
+            assert!(!r.async_contains(never_polled).await);
+        });
+    }
+
+    #[test]
+    fn test_async_contains_err() {
+        block_on(async {
+            let r: Result<i32, &str> = Err("fail");
+            assert!(r.async_contains_err(|| async { "fail" }).await);
+
+            let r: Result<i32, &str> = Err("fail");
+            assert!(!r.async_contains_err(|| async { "other" }).await);
+
+            let r: Result<i32, &str> = Ok(1);
+            assert!(!r.async_contains_err(never_polled).await);
+        });
+    }
+
+    #[test]
+    fn test_async_map_mut_and_then_mut() {
+        block_on(async {
+            let mut seen = Vec::new();
+            let mut record = |v: i32| {
+                seen.push(v);
+                async move { v * 10 }
+            };
+            let mut mapped = Vec::new();
+            for r in [Ok(1), Err("skip"), Ok(2), Ok(3)] {
+                mapped.push(r.async_map_mut(&mut record).await);
+            }
+            assert_eq!(mapped, [Ok(10), Err("skip"), Ok(20), Ok(30)]);
+            assert_eq!(seen, [1, 2, 3]);
+
+            let mut budget = 2;
+            let mut spend = |v: i32| {
+                let ok = budget > 0;
+                budget -= 1;
+                async move { if ok { Ok(v) } else { Err("over budget") } }
+            };
+            let mut chained = Vec::new();
+            for r in [Ok(1), Ok(2), Ok(3)] {
+                chained.push(r.async_and_then_mut(&mut spend).await);
+            }
+            assert_eq!(chained, [Ok(1), Ok(2), Err("over budget")]);
+        });
+    }
+
+    #[cfg(debug_assertions)]
+    #[test]
+    #[should_panic(expected = "async_map future polled after completion")]
+    fn test_poll_after_completion_names_combinator() {
+        let mut fut = pin!(Ok::<i32, &str>(1).async_map(|v| async move { v + 1 }));
+        assert_eq!(poll_once(fut.as_mut()), Poll::Ready(Ok(2)));
+        let _ = poll_once(fut.as_mut());
+    }
+
+    #[cfg(all(debug_assertions, feature = "stream"))]
+    #[test]
+    #[should_panic(expected = "async_try_for_each future polled after completion")]
+    fn test_poll_after_completion_names_try_for_each() {
+        use crate::stream::AsyncTryStreamExt;
+
+        let items = futures::stream::iter([Ok::<i32, &str>(1), Ok(2)]);
+        let mut fut = pin!(items.async_try_for_each(|_| async { Ok(()) }));
+        assert_eq!(poll_once(fut.as_mut()), Poll::Ready(Ok(())));
+        let _ = poll_once(fut.as_mut());
+    }
+
+    #[test]
+    fn test_async_and_then_ctx() {
+        block_on(async {
+            let names = vec!["zero", "one", "two"];
+            let r: Result<usize, &str> = Ok(2);
+            let name = r
+                .async_and_then_ctx(&names, |names, i| async move {
+                    names.get(i).copied().ok_or("out of range")
+                })
+                .await;
+            assert_eq!(name, Ok("two"));
+
+            let r: Result<usize, &str> = Ok(5);
+            let name = r
+                .async_and_then_ctx(names.as_slice(), |names, i| async move {
+                    names.get(i).copied().ok_or("out of range")
+                })
+                .await;
+            assert_eq!(name, Err("out of range"));
+
+            let r: Result<usize, &str> = Err("early");
+            let out: Result<(), _> = r.async_and_then_ctx(&names, |_, _| never_polled()).await;
+            assert_eq!(out, Err("early"));
+        });
+    }
+
+    #[test]
+    fn test_async_and_then_ctx_future_borrows_context() {
+        block_on(async {
+            let prefix = String::from("id-");
+            let fut = Ok::<_, ()>(7).async_and_then_ctx(prefix.as_str(), |prefix, v| async move {
+                // The inner future keeps the borrow across an await point.
+                core::future::ready(()).await;
+                Ok(format!("{prefix}{v}"))
+            });
+            // `prefix` outlives the future, which is awaited while it is borrowed.
+            assert_eq!(fut.await, Ok(String::from("id-7")));
+            assert_eq!(prefix, "id-");
+        });
+    }
+
+    #[test]
+    fn test_async_map_then_flattens() {
+        block_on(async {
+            let parse = |s: &'static str| async move { s.parse::<i32>().map_err(|_| s) };
+
+            let r: Result<&str, &str> = Ok("4");
+            let nested = r.async_map(parse).await;
+            assert_eq!(nested, Ok(Ok(4)));
+
+            let r: Result<&str, &str> = Ok("4");
+            assert_eq!(r.async_map_then(parse).await, Ok(4));
+
+            let r: Result<&str, &str> = Ok("x");
+            assert_eq!(r.async_map_then(parse).await, Err("x"));
+
+            let r: Result<&str, &str> = Err("early");
+            let res: Result<i32, _> = r.async_map_then(|_| never_polled()).await;
+            assert_eq!(res, Err("early"));
+        });
+    }
+
+    #[test]
+    fn test_cancel_before_first_poll() {
+        let dropped = Cell::new(false);
+        let called = Cell::new(false);
+        {
+            let r: Result<DropFlag, ()> = Ok(DropFlag(&dropped));
+            let _fut = r.async_map(|flag| {
+                called.set(true);
+                async move { flag }
+            });
+        }
+        assert!(dropped.get());
+        assert!(!called.get());
+    }
+
+    #[test]
+    fn test_cancel_async_map_at_await_point() {
+        let dropped = Cell::new(false);
+        let finished = Cell::new(false);
+        {
+            let r: Result<DropFlag, ()> = Ok(DropFlag(&dropped));
+            let mut fut = pin!(r.async_map(|flag| async {
+                yield_now().await;
+                finished.set(true);
+                flag
+            }));
+            assert!(poll_once(fut.as_mut()).is_pending());
+            assert!(!dropped.get());
+        }
+        assert!(dropped.get());
+        assert!(!finished.get());
+    }
+
+    #[test]
+    fn test_cancel_async_or_else_at_await_point() {
+        let dropped = Cell::new(false);
+        let finished = Cell::new(false);
+        {
+            let r: Result<i32, DropFlag> = Err(DropFlag(&dropped));
+            let mut fut = pin!(r.async_or_else(|flag| async {
+                yield_now().await;
+                finished.set(true);
+                Err::<i32, _>(flag)
+            }));
+            assert!(poll_once(fut.as_mut()).is_pending());
+        }
+        assert!(dropped.get());
+        assert!(!finished.get());
+    }
+
+    #[test]
+    fn test_cancel_async_map_or_else_concurrent_drops_both() {
+        let op_dropped = Cell::new(false);
+        let default_dropped = Cell::new(false);
+        let finished = Cell::new(false);
+        {
+            let r: Result<i32, ()> = Ok(1);
+            let mut fut = pin!(r.async_map_or_else_concurrent(
+                || {
+                    let flag = DropFlag(&default_dropped);
+                    async {
+                        let _flag = flag;
+                        yield_now().await;
+                        finished.set(true);
+                        0
+                    }
+                },
+                |v| {
+                    let flag = DropFlag(&op_dropped);
+                    async move {
+                        let _flag = flag;
+                        yield_now().await;
+                        yield_now().await;
+                        v
+                    }
+                },
+            ));
+            assert!(poll_once(fut.as_mut()).is_pending());
+            assert!(!op_dropped.get() && !default_dropped.get());
+        }
+        assert!(op_dropped.get());
+        assert!(default_dropped.get());
+        assert!(!finished.get());
+    }
+
+    #[cfg(feature = "futures")]
+    #[test]
+    fn test_async_split() {
+        use futures_util::future::Either;
+
+        block_on(async {
+            let r: Result<i32, &str> = Ok(3);
+            let res = r
+                .async_split(|v| async move { v * 2 }, |_| never_polled::<String>())
+                .await;
+            assert!(matches!(res, Either::Left(6)));
+
+            let r: Result<i32, &str> = Err("fail");
+            let res = r
+                .async_split(|_| never_polled::<i32>(), |e| async move { e.to_string() })
+                .await;
+            assert!(matches!(res, Either::Right(msg) if msg == "fail"));
+        });
+    }
+
+    #[test]
+    fn test_async_try_into() {
+        #[derive(Debug, PartialEq)]
+        enum ConvertError {
+            Upstream(&'static str),
+            OutOfRange(i64),
+        }
+
+        impl From<&'static str> for ConvertError {
+            fn from(err: &'static str) -> Self {
+                ConvertError::Upstream(err)
+            }
+        }
+
+        let to_byte =
+            |v: i64| async move { u8::try_from(v).map_err(|_| ConvertError::OutOfRange(v)) };
+
+        block_on(async {
+            let r: Result<i64, &str> = Ok(42);
+            assert_eq!(r.async_try_into(to_byte).await, Ok(42u8));
+
+            let r: Result<i64, &str> = Ok(-1);
+            assert_eq!(
+                r.async_try_into(to_byte).await,
+                Err(ConvertError::OutOfRange(-1))
+            );
+
+            let r: Result<i64, &str> = Err("offline");
+            let res: Result<u8, ConvertError> = r.async_try_into(|_| never_polled()).await;
+            assert_eq!(res, Err(ConvertError::Upstream("offline")));
+        });
+    }
+
+    #[test]
+    fn test_async_map_timed() {
+        block_on(async {
+            let now = Cell::new(Duration::from_millis(100));
+            let clock = || now.get();
+
+            let r: Result<i32, &str> = Ok(2);
+            let (res, took) = r
+                .async_map_timed(clock, |v| {
+                    let now = &now;
+                    async move {
+                        now.set(now.get() + Duration::from_millis(25));
+                        v * 2
+                    }
+                })
+                .await;
+            assert_eq!(res, Ok(4));
+            assert_eq!(took, Some(Duration::from_millis(25)));
+
+            let r: Result<i32, &str> = Err("fail");
+            let (res, took) = r
+                .async_map_timed(|| -> Duration { unreachable!() }, |_| never_polled::<i32>())
+                .await;
+            assert_eq!(res, Err("fail"));
+            assert_eq!(took, None);
+        });
+    }
+
+    #[test]
+    fn test_async_or_default_with() {
+        block_on(async {
+            let r: Result<i32, &str> = Err("missing");
+            let res = r
+                .async_or_default_with(|e| {
+                    let recover = *e == "missing";
+                    async move { recover.then_some(0) }
+                })
+                .await;
+            assert_eq!(res, Ok(0));
+
+            let mut seen = None;
+            let r: Result<i32, &str> = Err("fatal");
+            let res = r
+                .async_or_default_with(|e| {
+                    seen = Some(*e);
+                    async { None }
+                })
+                .await;
+            assert_eq!(res, Err("fatal"));
+            assert_eq!(seen, Some("fatal"));
+
+            let r: Result<i32, &str> = Ok(5);
+            assert_eq!(r.async_or_default_with(|_| never_polled()).await, Ok(5));
+        });
+    }
+
+    #[test]
+    fn test_async_replace_err() {
+        block_on(async {
+            let dropped = Cell::new(false);
+            let r: Result<i32, DropFlag> = Err(DropFlag(&dropped));
+            let res = r
+                .async_replace_err(|| {
+                    assert!(dropped.get());
+                    async { "sanitized" }
+                })
+                .await;
+            assert_eq!(res, Err("sanitized"));
+
+            let r: Result<i32, &str> = Ok(3);
+            let res: Result<i32, &str> = r.async_replace_err(never_polled).await;
+            assert_eq!(res, Ok(3));
+        });
+    }
+
+    #[test]
+    fn test_async_then_unit() {
+        block_on(async {
+            let mut consumed = Vec::new();
+            let r: Result<String, &str> = Ok("job".to_string());
+            let res = r
+                .async_then_unit(|v| {
+                    consumed.push(v);
+                    async {}
+                })
+                .await;
+            assert_eq!(res, Ok(()));
+            assert_eq!(consumed, ["job"]);
+
+            let r: Result<String, &str> = Err("fail");
+            assert_eq!(r.async_then_unit(|_| never_polled()).await, Err("fail"));
+        });
+    }
+
+    #[test]
+    fn test_async_and_then_ref() {
+        block_on(async {
+            let r: Result<Vec<i32>, &str> = Ok(vec![1, 2, 3]);
+            let res = r
+                .async_and_then_ref(|items| {
+                    let sum: i32 = items.iter().sum();
+                    async move { Ok(sum) }
+                })
+                .await;
+            assert_eq!(res, Ok((vec![1, 2, 3], 6)));
+
+            let r: Result<Vec<i32>, &str> = Ok(vec![]);
+            let res: Result<(Vec<i32>, i32), _> =
+                r.async_and_then_ref(|_| async { Err("empty") }).await;
+            assert_eq!(res, Err("empty"));
+
+            let r: Result<Vec<i32>, &str> = Err("fail");
+            let res: Result<(Vec<i32>, i32), _> = r.async_and_then_ref(|_| never_polled()).await;
+            assert_eq!(res, Err("fail"));
+        });
+    }
+
+    #[test]
+    fn test_async_ok_and_err() {
+        block_on(async {
+            let r: Result<i32, &str> = Ok(1);
+            assert_eq!(r.async_ok().await, Some(1));
+            assert_eq!(r.async_err().await, None);
+
+            let r: Result<i32, &str> = Err("fail");
+            assert_eq!(r.async_ok().await, None);
+            assert_eq!(r.async_err().await, Some("fail"));
+        });
+    }
+
+    #[test]
+    fn test_async_and_then_timeout() {
+        let sleep = |dur: Duration| after(dur.as_secs() as usize);
+
+        block_on(async {
+            let r: Result<u64, &str> = Ok(2);
+            let res = r
+                .async_and_then_timeout(
+                    Duration::from_secs(5),
+                    sleep,
+                    || "timeout",
+                    |v| async move {
+                        after(v as usize).await;
+                        Ok(v * 10)
+                    },
+                )
+                .await;
+            assert_eq!(res, Ok(20));
+
+            let r: Result<u64, &str> = Ok(9);
+            let res = r
+                .async_and_then_timeout(
+                    Duration::from_secs(5),
+                    sleep,
+                    || "timeout",
+                    |v| async move {
+                        after(v as usize).await;
+                        Ok(v * 10)
+                    },
+                )
+                .await;
+            assert_eq!(res, Err("timeout"));
+
+            let r: Result<u64, &str> = Ok(1);
+            let res: Result<u64, _> = r
+                .async_and_then_timeout(
+                    Duration::from_secs(5),
+                    sleep,
+                    || "timeout",
+                    |_| async { Err("failed") },
+                )
+                .await;
+            assert_eq!(res, Err("failed"));
+
+            let r: Result<u64, &str> = Err("early");
+            let res: Result<u64, _> = r
+                .async_and_then_timeout(
+                    Duration::from_secs(5),
+                    |_| never_polled::<()>(),
+                    || unreachable!(),
+                    |_| never_polled(),
+                )
+                .await;
+            assert_eq!(res, Err("early"));
+        });
+    }
+
+    #[test]
+    fn test_cancel_async_and_then_timeout_drops_both() {
+        let step_dropped = Cell::new(false);
+        let sleep_dropped = Cell::new(false);
+        {
+            let mut fut = pin!(Ok::<u64, &str>(3).async_and_then_timeout(
+                Duration::from_secs(5),
+                |dur| {
+                    let flag = DropFlag(&sleep_dropped);
+                    async move {
+                        let _flag = flag;
+                        after(dur.as_secs() as usize).await;
+                    }
+                },
+                || "timeout",
+                |v| {
+                    let flag = DropFlag(&step_dropped);
+                    async move {
+                        let _flag = flag;
+                        after(v as usize).await;
+                        Ok(v)
+                    }
+                },
+            ));
+            assert!(poll_once(fut.as_mut()).is_pending());
+        }
+        assert!(step_dropped.get());
+        assert!(sleep_dropped.get());
+    }
+
+    #[test]
+    fn test_async_or_else_into() {
+        #[derive(Debug, PartialEq)]
+        enum AppError {
+            Fallback(&'static str),
+        }
+
+        impl From<&'static str> for AppError {
+            fn from(err: &'static str) -> Self {
+                AppError::Fallback(err)
+            }
+        }
+
+        block_on(async {
+            let r: Result<i32, u16> = Err(404);
+            let res: Result<i32, AppError> = r
+                .async_or_else_into(|status| async move { Ok::<_, &str>(i32::from(status)) })
+                .await;
+            assert_eq!(res, Ok(404));
+
+            let r: Result<i32, u16> = Err(500);
+            let res: Result<i32, AppError> = r
+                .async_or_else_into(|_| async { Err("fallback down") })
+                .await;
+            assert_eq!(res, Err(AppError::Fallback("fallback down")));
+
+            let r: Result<i32, u16> = Ok(1);
+            let res: Result<i32, AppError> = r
+                .async_or_else_into(|_| never_polled::<Result<i32, &str>>())
+                .await;
+            assert_eq!(res, Ok(1));
+        });
+    }
+
+    #[cfg(all(feature = "futures", feature = "std"))]
+    #[test]
+    fn test_async_and_then_catch() {
+        block_on(async {
+            let r: Result<i32, &str> = Ok(2);
+            let res = r
+                .async_and_then_catch(|v| async move { Ok(v * 3) }, || unreachable!())
+                .await;
+            assert_eq!(res, Ok(6));
+
+            let r: Result<i32, &str> = Ok(2);
+            let res: Result<i32, &str> = r
+                .async_and_then_catch(|_| async { panic!("boom") }, || "panicked")
+                .await;
+            assert_eq!(res, Err("panicked"));
+
+            let r: Result<i32, &str> = Err("early");
+            let res = r
+                .async_and_then_catch(|_| never_polled::<Result<i32, _>>(), || unreachable!())
+                .await;
+            assert_eq!(res, Err("early"));
+        });
+    }
+
+    #[test]
+    fn test_async_map_async() {
+        block_on(async {
+            let suffix = String::from("!");
+            let r: Result<&str, &str> = Ok("hi");
+            let res = r
+                .async_map_async(async |v| {
+                    yield_now().await;
+                    format!("{v}{suffix}")
+                })
+                .await;
+            assert_eq!(res.as_deref(), Ok("hi!"));
+
+            let r: Result<&str, &str> = Err("fail");
+            let res = r
+                .async_map_async(async |_| -> usize { unreachable!() })
+                .await;
+            assert_eq!(res, Err("fail"));
+        });
+    }
+
+    #[test]
+    fn test_async_map_or_async_default() {
+        block_on(async {
+            let r: Result<i32, &str> = Ok(2);
+            let res = r
+                .async_map_or_async_default(core::future::pending, |v| async move { v * 10 })
+                .await;
+            assert_eq!(res, 20);
+
+            let r: Result<i32, &str> = Err("fail");
+            let res = r
+                .async_map_or_async_default(|| async { -1 }, |_| never_polled())
+                .await;
+            assert_eq!(res, -1);
+        });
+    }
+
+    #[test]
+    fn test_async_map_or_async_default_starts_default() {
+        let started = Cell::new(false);
+        let default_dropped = Cell::new(false);
+        block_on(async {
+            let r: Result<i32, &str> = Ok(2);
+            let res = r
+                .async_map_or_async_default(
+                    || {
+                        let flag = DropFlag(&default_dropped);
+                        let started = &started;
+                        async move {
+                            let _flag = flag;
+                            started.set(true);
+                            core::future::pending::<i32>().await
+                        }
+                    },
+                    |v| async move {
+                        yield_now().await;
+                        v + 1
+                    },
+                )
+                .await;
+            assert_eq!(res, 3);
+        });
+        assert!(
+            started.get(),
+            "default future runs before the branch completes"
+        );
+        assert!(default_dropped.get());
+    }
+
+    #[test]
+    fn test_async_map_err_inspect() {
+        block_on(async {
+            let mut seen = None;
+            let r: Result<i32, &str> = Ok(7);
+            let res = r
+                .async_map_err_inspect(
+                    |_| never_polled::<usize>(),
+                    |v| {
+                        seen = Some(*v);
+                        yield_now()
+                    },
+                )
+                .await;
+            assert_eq!(res, Ok(7));
+            assert_eq!(seen, Some(7));
+
+            let r: Result<i32, &str> = Err("fail");
+            let res = r
+                .async_map_err_inspect(|e| async move { e.len() }, |_| never_polled())
+                .await;
+            assert_eq!(res, Err(4));
+        });
+    }
+
+    #[test]
+    fn test_async_and_then_retry_succeeds_after_failures() {
+        block_on(async {
+            let mut seen = Vec::new();
+            let r: Result<String, &str> = Ok("job".to_string());
+            let res = r
+                .async_and_then_retry(5, |name| {
+                    seen.push(name.clone());
+                    let attempt = seen.len();
+                    async move {
+                        yield_now().await;
+                        if attempt < 3 {
+                            Err("busy")
+                        } else {
+                            Ok(format!("{name}#{attempt}"))
+                        }
+                    }
+                })
+                .await;
+            assert_eq!(res.as_deref(), Ok("job#3"));
+            assert_eq!(seen, ["job", "job", "job"]);
+        });
+    }
+
+    #[test]
+    fn test_async_and_then_retry_exhausted() {
+        block_on(async {
+            let mut calls = 0;
+            let r: Result<i32, i32> = Ok(1);
+            let res: Result<(), i32> = r
+                .async_and_then_retry(3, |_| {
+                    calls += 1;
+                    let attempt = calls;
+                    async move { Err(attempt) }
+                })
+                .await;
+            assert_eq!(res, Err(3));
+            assert_eq!(calls, 3);
+
+            calls = 0;
+            let res: Result<(), i32> = r
+                .async_and_then_retry(0, |_| {
+                    calls += 1;
+                    async { Err(0) }
+                })
+                .await;
+            assert_eq!(res, Err(0));
+            assert_eq!(calls, 1);
+
+            let r: Result<i32, i32> = Err(-1);
+            let res = r
+                .async_and_then_retry(3, |_| never_polled::<Result<(), i32>>())
+                .await;
+            assert_eq!(res, Err(-1));
+        });
+    }
+
+    #[cfg(feature = "tokio")]
+    #[test]
+    fn test_tokio_runtime_compat() {
+        let rt = tokio::runtime::Builder::new_current_thread()
+            .build()
+            .unwrap();
+        let res = rt.block_on(async {
+            Ok::<i32, &str>(2)
+                .async_and_then(|v| async move {
+                    tokio::task::yield_now().await;
+                    Ok(v * 2)
+                })
+                .await
+        });
+        assert_eq!(res, Ok(4));
+    }
+
+    /// Tests driven by an executor built from `core` alone, mirroring how the
+    /// crate is used on targets without `std`.
+    mod core_executor {
+        use super::*;
+        use core::pin::pin;
+        use core::task::{Context, Poll, Waker};
+
+        fn block_on<F: Future>(fut: F) -> F::Output {
+            let mut fut = pin!(fut);
+            let mut cx = Context::from_waker(Waker::noop());
+            loop {
+                if let Poll::Ready(output) = fut.as_mut().poll(&mut cx) {
+                    return output;
+                }
+            }
+        }
+
+        #[test]
+        fn test_async_result_combinators() {
+            let r: Result<i32, &str> = Ok(2);
+            let res = block_on(async {
+                r.async_map(|v| async move {
+                    yield_now().await;
+                    v * 3
+                })
+                .await
+                .async_and_then(|v| async move { if v > 5 { Ok(v) } else { Err("small") } })
+                .await
+                .async_map_err(|e| async move { e.len() })
+                .await
+            });
+            assert_eq!(res, Ok(6));
+
+            let r: Result<i32, &str> = Err("fail");
+            let res = block_on(r.async_unwrap_or_else(|e| async move {
+                yield_now().await;
+                e.len() as i32
+            }));
+            assert_eq!(res, 4);
+        }
+
+        #[test]
+        fn test_async_option_combinators() {
+            let res = block_on(async {
+                Some(4)
+                    .async_filter(|v| {
+                        let even = *v % 2 == 0;
+                        async move {
+                            yield_now().await;
+                            even
+                        }
+                    })
+                    .await
+                    .async_map(|v| async move { v + 1 })
+                    .await
+            });
+            assert_eq!(res, Some(5));
+        }
     }
 }
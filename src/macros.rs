@@ -0,0 +1,158 @@
+/// Chains async fallible steps, stopping at the first error like `?`.
+///
+/// `async_try!(init, step, ...)` takes an initial `Result` followed by any
+/// number of steps, each a closure `FnOnce(T) -> impl Future<Output = Result<U, O>>`
+/// whose error type `O` implements `From` for the error of the previous step.
+/// It evaluates to a future of the final step's `Result`.
+///
+/// The macro expands to an `async` block that threads the result through
+/// [`async_and_then_into`](crate::AsyncResultExt::async_and_then_into):
+///
+/// ```ignore
+/// async {
+///     let result = init;
+///     let result = AsyncResultExt::async_and_then_into(result, step_1).await;
+///     let result = AsyncResultExt::async_and_then_into(result, step_2).await;
+///     result
+/// }
+/// ```
+///
+/// Once a step fails, the later closures are never called; the error is only
+/// converted into each later step's error type. Because every step may pick its
+/// own error type, annotate the final `Result` (or the steps' errors) when
+/// inference cannot settle them.
+///
+/// ```
+/// use async_result_ext::async_try;
+///
+/// #[derive(Debug, PartialEq)]
+/// struct AppError(String);
+///
+/// impl From<std::num::ParseIntError> for AppError {
+///     fn from(err: std::num::ParseIntError) -> Self {
+///         AppError(err.to_string())
+///     }
+/// }
+///
+/// # futures::executor::block_on(async {
+/// let total: Result<i32, AppError> = async_try!(
+///     Ok::<_, std::num::ParseIntError>("20"),
+///     |s: &str| async move { s.parse::<i32>() },
+///     |v| async move {
+///         if v > 0 { Ok(v + 1) } else { Err(AppError("not positive".into())) }
+///     },
+/// )
+/// .await;
+/// assert_eq!(total, Ok(21));
+/// # });
+/// ```
+#[macro_export]
+macro_rules! async_try {
+    ($init:expr $(, $step:expr)* $(,)?) => {
+        async {
+            let result = $init;
+            $(
+                let result = $crate::AsyncResultExt::async_and_then_into(result, $step).await;
+            )*
+            result
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tests::never_polled;
+    use futures::executor::block_on;
+
+    #[derive(Debug, PartialEq)]
+    struct ParseError(&'static str);
+
+    #[derive(Debug, PartialEq)]
+    enum StepError {
+        Parse(&'static str),
+        Range(i32),
+    }
+
+    impl From<ParseError> for StepError {
+        fn from(err: ParseError) -> Self {
+            StepError::Parse(err.0)
+        }
+    }
+
+    #[derive(Debug, PartialEq)]
+    enum AppError {
+        Step(StepError),
+        Save,
+    }
+
+    impl From<StepError> for AppError {
+        fn from(err: StepError) -> Self {
+            AppError::Step(err)
+        }
+    }
+
+    async fn pipeline(
+        input: Result<&'static str, ParseError>,
+        save_ok: bool,
+    ) -> Result<i32, AppError> {
+        async_try!(
+            input,
+            |s: &str| async move { s.parse::<i32>().map_err(|_| ParseError(s)) },
+            |v| async move {
+                if v < 100 {
+                    Ok(v * 2)
+                } else {
+                    Err(StepError::Range(v))
+                }
+            },
+            |v| async move { if save_ok { Ok(v) } else { Err(AppError::Save) } },
+        )
+        .await
+    }
+
+    #[test]
+    fn test_async_try_happy_path() {
+        block_on(async {
+            assert_eq!(pipeline(Ok("21"), true).await, Ok(42));
+        });
+    }
+
+    #[test]
+    fn test_async_try_fails_at_each_step() {
+        block_on(async {
+            assert_eq!(
+                pipeline(Err(ParseError("input")), true).await,
+                Err(AppError::Step(StepError::Parse("input")))
+            );
+            assert_eq!(
+                pipeline(Ok("x"), true).await,
+                Err(AppError::Step(StepError::Parse("x")))
+            );
+            assert_eq!(
+                pipeline(Ok("500"), true).await,
+                Err(AppError::Step(StepError::Range(500)))
+            );
+            assert_eq!(pipeline(Ok("1"), false).await, Err(AppError::Save));
+        });
+    }
+
+    #[test]
+    fn test_async_try_skips_steps_after_error() {
+        block_on(async {
+            let res: Result<i32, StepError> = async_try!(
+                Err::<i32, _>(ParseError("early")),
+                |_| never_polled::<Result<i32, StepError>>(),
+                |_: i32| never_polled::<Result<i32, StepError>>(),
+            )
+            .await;
+            assert_eq!(res, Err(StepError::Parse("early")));
+        });
+    }
+
+    #[test]
+    fn test_async_try_without_steps() {
+        block_on(async {
+            assert_eq!(async_try!(Ok::<_, ()>(3)).await, Ok(3));
+        });
+    }
+}
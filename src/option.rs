@@ -0,0 +1,1240 @@
+//! Async extensions for [`Option<T>`] and the named futures they return.
+
+use core::future::{Future, Ready, ready};
+use core::pin::Pin;
+use core::task::{Context, Poll};
+
+use pin_project_lite::pin_project;
+
+use crate::AsyncFlattenExt;
+use crate::state::{State, Step};
+
+/// Asynchronous extensions for [`Option<T>`].
+///
+/// This trait provides async counterparts of common `Option` methods
+/// (`map`, `and_then`) that accept asynchronous closures.
+///
+/// Example:
+/// ```
+/// use async_result_ext::AsyncOptionExt;
+///
+/// // Any executor works; this example uses `futures`.
+/// futures::executor::block_on(async {
+///     let o: Option<i32> = Some(2);
+///
+///     let doubled = o.async_map(|v| async move { v * 2 }).await;
+///     assert_eq!(doubled, Some(4));
+/// });
+/// ```
+///
+/// # Cancellation
+///
+/// As with [`AsyncResultExt`](crate::AsyncResultExt), dropping a returned future
+/// at any point drops the captured option, closures and pending closure future
+/// without calling anything further. The methods on `&mut Option<T>` describe
+/// what state the option is left in.
+///
+/// # Methods that complete immediately
+///
+/// [`async_ok_or`](Self::async_ok_or) and [`async_or_default`](Self::async_or_default)
+/// have nothing to await and return an already completed [`Ready`], for the
+/// reason given in the [`AsyncResultExt` docs](crate::AsyncResultExt#methods-that-complete-immediately).
+pub trait AsyncOptionExt<T> {
+    /// Asynchronous version of [`Option::map`].
+    ///
+    /// Applies an async function `op` to the contained value.
+    /// If the option is `None`, it is returned unchanged.
+    ///
+    /// ```
+    /// use async_result_ext::AsyncOptionExt;
+    ///
+    /// # futures::executor::block_on(async {
+    /// let o: Option<i32> = Some(5);
+    /// let res = o.async_map(|v| async move { v + 1 }).await;
+    /// assert_eq!(res, Some(6));
+    /// # });
+    /// ```
+    fn async_map<U, F, Fut>(self, op: F) -> AsyncMap<T, F, Fut>
+    where
+        F: FnOnce(T) -> Fut,
+        Fut: Future<Output = U>;
+
+    /// Asynchronous version of [`Option::and_then`].
+    ///
+    /// Chains async computations that return `Option`.
+    ///
+    /// ```
+    /// use async_result_ext::AsyncOptionExt;
+    ///
+    /// # futures::executor::block_on(async {
+    /// let o: Option<i32> = Some(2);
+    /// let res = o.async_and_then(|v| async move { Some(v * 3) }).await;
+    /// assert_eq!(res, Some(6));
+    /// # });
+    /// ```
+    fn async_and_then<U, F, Fut>(self, op: F) -> AsyncAndThen<T, F, Fut>
+    where
+        F: FnOnce(T) -> Fut,
+        Fut: Future<Output = Option<U>>;
+
+    /// Asynchronous version of [`Option::unwrap_or_else`].
+    ///
+    /// Returns the contained value, or awaits `op` to compute a fallback.
+    /// If the option is `Some`, `op` is never called.
+    ///
+    /// ```
+    /// use async_result_ext::AsyncOptionExt;
+    ///
+    /// # futures::executor::block_on(async {
+    /// let o: Option<i32> = None;
+    /// let res = o.async_unwrap_or_else(|| async { 7 }).await;
+    /// assert_eq!(res, 7);
+    /// # });
+    /// ```
+    fn async_unwrap_or_else<F, Fut>(self, op: F) -> AsyncUnwrapOrElse<T, F, Fut>
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = T>;
+
+    /// Asynchronous version of [`Option::or_else`].
+    ///
+    /// Returns the option if it is `Some`, otherwise awaits `op` for a replacement.
+    fn async_or_else<F, Fut>(self, op: F) -> AsyncOrElse<T, F, Fut>
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = Option<T>>;
+
+    /// Asynchronous version of [`Option::get_or_insert_with`].
+    ///
+    /// If the option is `None`, awaits `op` and stores its output.
+    /// Returns a mutable reference to the contained value either way.
+    ///
+    /// # Cancellation
+    ///
+    /// Dropping the future before `op` completes leaves the option `None`.
+    ///
+    /// ```
+    /// use async_result_ext::AsyncOptionExt;
+    ///
+    /// # futures::executor::block_on(async {
+    /// let mut cache: Option<String> = None;
+    /// let handle = cache.async_get_or_insert_with(|| async { "connected".to_string() }).await;
+    /// handle.push('!');
+    /// assert_eq!(cache.as_deref(), Some("connected!"));
+    /// # });
+    /// ```
+    fn async_get_or_insert_with<'a, F, Fut>(
+        &'a mut self,
+        op: F,
+    ) -> AsyncGetOrInsertWith<'a, T, F, Fut>
+    where
+        T: 'a,
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = T>;
+
+    /// Asynchronous version of [`Option::filter`].
+    ///
+    /// Returns `Some` only if the option is `Some` **and** the async `predicate` returns `true`.
+    /// The predicate borrows the value so it can be returned unchanged.
+    fn async_filter<F, Fut>(self, predicate: F) -> AsyncFilter<T, F, Fut>
+    where
+        F: FnOnce(&T) -> Fut,
+        Fut: Future<Output = bool>;
+
+    /// Asynchronous version of [`Option::is_some_and`].
+    ///
+    /// Returns `true` if the option is `Some` **and** the async predicate returns `true`.
+    /// Returns `false` if the option is `None` or the predicate resolves to `false`.
+    fn async_is_some_and<F, Fut>(self, op: F) -> AsyncIsSomeAnd<T, F, Fut>
+    where
+        F: FnOnce(T) -> Fut,
+        Fut: Future<Output = bool>;
+
+    /// Asynchronous version of [`Option::is_none_or`].
+    ///
+    /// Returns `true` if the option is `None` **or** the async predicate returns `true`.
+    /// Returns `false` only if the option is `Some` and the predicate resolves to `false`.
+    fn async_is_none_or<F, Fut>(self, op: F) -> AsyncIsNoneOr<T, F, Fut>
+    where
+        F: FnOnce(T) -> Fut,
+        Fut: Future<Output = bool>;
+
+    /// Asynchronous version of [`Option::ok_or_else`].
+    ///
+    /// Converts `Some(v)` into `Ok(v)`, or awaits `err` to build the `Err` value for `None`.
+    ///
+    /// ```
+    /// use async_result_ext::{AsyncOptionExt, AsyncResultExt};
+    ///
+    /// # futures::executor::block_on(async {
+    /// let cached: Option<i32> = None;
+    /// let res = cached
+    ///     .async_ok_or_else(|| async { "not cached" })
+    ///     .await
+    ///     .async_map_err(|e| async move { e.len() })
+    ///     .await;
+    /// assert_eq!(res, Err(10));
+    /// # });
+    /// ```
+    fn async_ok_or_else<E, F, Fut>(self, err: F) -> AsyncOkOrElse<T, F, Fut>
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = E>;
+
+    /// Asynchronous version of [`Option::zip_with`].
+    ///
+    /// If both options are `Some`, awaits `op` on the two values.
+    /// Otherwise returns `None` without calling `op`.
+    fn async_zip_with<U, R, F, Fut>(self, other: Option<U>, op: F) -> AsyncZipWith<T, U, F, Fut>
+    where
+        F: FnOnce(T, U) -> Fut,
+        Fut: Future<Output = R>;
+
+    /// Asynchronous version of [`Option::ok_or`].
+    ///
+    /// Converts `Some(v)` into `Ok(v)` and `None` into `Err(err)`. Completes
+    /// immediately; see the trait docs.
+    ///
+    /// ```
+    /// use async_result_ext::AsyncOptionExt;
+    ///
+    /// # futures::executor::block_on(async {
+    /// let cached: Option<i32> = None;
+    /// assert_eq!(cached.async_ok_or("not cached").await, Err("not cached"));
+    /// # });
+    /// ```
+    fn async_ok_or<E>(self, err: E) -> Ready<Result<T, E>>;
+
+    /// Asynchronous version of [`Option::inspect`].
+    ///
+    /// Awaits `op` with a reference to the contained value, then returns the
+    /// option unchanged. If the option is `None`, `op` is never called.
+    ///
+    /// ```
+    /// use async_result_ext::AsyncOptionExt;
+    ///
+    /// # futures::executor::block_on(async {
+    /// let hit: Option<&str> = Some("cached");
+    /// let res = hit
+    ///     .async_inspect(|v| {
+    ///         let key = v.to_string();
+    ///         async move { println!("cache hit: {key}") }
+    ///     })
+    ///     .await;
+    /// assert_eq!(res, Some("cached"));
+    /// # });
+    /// ```
+    fn async_inspect<F, Fut>(self, op: F) -> AsyncInspect<T, F, Fut>
+    where
+        F: FnOnce(&T) -> Fut,
+        Fut: Future<Output = ()>;
+
+    /// Asynchronous version of [`Option::take_if`].
+    ///
+    /// Awaits `predicate` with a mutable reference to the contained value. If it
+    /// returns `true`, the value is taken out, leaving `None` in its place;
+    /// otherwise the option is left as is and `None` is returned. The predicate
+    /// may modify the value either way.
+    ///
+    /// # Cancellation
+    ///
+    /// Dropping the future before the predicate's future completes leaves the
+    /// value in the option, including any changes the predicate made to it.
+    ///
+    /// ```
+    /// use async_result_ext::AsyncOptionExt;
+    ///
+    /// # futures::executor::block_on(async {
+    /// let mut cache: Option<u32> = Some(7);
+    /// let stale = cache.async_take_if(|age| {
+    ///     let expired = *age > 5;
+    ///     async move { expired }
+    /// });
+    /// assert_eq!(stale.await, Some(7));
+    /// assert_eq!(cache, None);
+    /// # });
+    /// ```
+    fn async_take_if<'a, F, Fut>(&'a mut self, predicate: F) -> AsyncTakeIf<'a, T, F, Fut>
+    where
+        T: 'a,
+        F: FnOnce(&mut T) -> Fut,
+        Fut: Future<Output = bool>;
+
+    /// Asynchronous version of [`Option::unwrap_or_default`].
+    ///
+    /// Returns the contained value, or `T::default()` if the option is `None`.
+    /// This is the `Option` counterpart of
+    /// [`AsyncResultExt::async_unwrap_or_default`](crate::AsyncResultExt::async_unwrap_or_default).
+    /// Completes immediately; see the trait docs.
+    ///
+    /// ```
+    /// use async_result_ext::AsyncOptionExt;
+    ///
+    /// # futures::executor::block_on(async {
+    /// let name: Option<&str> = None;
+    /// let greeting = name
+    ///     .async_map(|n| async move { format!("hello, {n}") })
+    ///     .await
+    ///     .async_or_default()
+    ///     .await;
+    /// assert_eq!(greeting, "");
+    /// # });
+    /// ```
+    fn async_or_default(self) -> Ready<T>
+    where
+        T: Default;
+
+    /// Asynchronous version of [`Option::xor`] with a tie-break.
+    ///
+    /// Returns the value of whichever option is `Some` if exactly one is, and
+    /// `None` if neither is. If both are `Some`, awaits `tie_break` on the two
+    /// values, `self`'s first, and returns its output: `None` to match
+    /// [`Option::xor`], or either value to resolve the conflict.
+    ///
+    /// ```
+    /// use async_result_ext::AsyncOptionExt;
+    ///
+    /// # futures::executor::block_on(async {
+    /// let cli_port = Some(8080);
+    /// let env_port = Some(9090);
+    /// let port = cli_port
+    ///     .async_xor(env_port, |cli, env| async move {
+    ///         eprintln!("port set twice ({cli} and {env}), using the command line");
+    ///         Some(cli)
+    ///     })
+    ///     .await;
+    /// assert_eq!(port, Some(8080));
+    /// # });
+    /// ```
+    fn async_xor<F, Fut>(self, other: Option<T>, tie_break: F) -> AsyncXor<T, F, Fut>
+    where
+        F: FnOnce(T, T) -> Fut,
+        Fut: Future<Output = Option<T>>;
+}
+
+impl<T> AsyncOptionExt<T> for Option<T> {
+    fn async_map<U, F, Fut>(self, op: F) -> AsyncMap<T, F, Fut>
+    where
+        F: FnOnce(T) -> Fut,
+        Fut: Future<Output = U>,
+    {
+        AsyncMap::new(self, op)
+    }
+
+    fn async_and_then<U, F, Fut>(self, op: F) -> AsyncAndThen<T, F, Fut>
+    where
+        F: FnOnce(T) -> Fut,
+        Fut: Future<Output = Option<U>>,
+    {
+        AsyncAndThen::new(self, op)
+    }
+
+    fn async_unwrap_or_else<F, Fut>(self, op: F) -> AsyncUnwrapOrElse<T, F, Fut>
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = T>,
+    {
+        AsyncUnwrapOrElse::new(self, op)
+    }
+
+    fn async_or_else<F, Fut>(self, op: F) -> AsyncOrElse<T, F, Fut>
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = Option<T>>,
+    {
+        AsyncOrElse::new(self, op)
+    }
+
+    fn async_get_or_insert_with<'a, F, Fut>(
+        &'a mut self,
+        op: F,
+    ) -> AsyncGetOrInsertWith<'a, T, F, Fut>
+    where
+        T: 'a,
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = T>,
+    {
+        AsyncGetOrInsertWith::new(self, op)
+    }
+
+    fn async_filter<F, Fut>(self, predicate: F) -> AsyncFilter<T, F, Fut>
+    where
+        F: FnOnce(&T) -> Fut,
+        Fut: Future<Output = bool>,
+    {
+        AsyncFilter::new(self, predicate)
+    }
+
+    fn async_is_some_and<F, Fut>(self, op: F) -> AsyncIsSomeAnd<T, F, Fut>
+    where
+        F: FnOnce(T) -> Fut,
+        Fut: Future<Output = bool>,
+    {
+        AsyncIsSomeAnd::new(self, op)
+    }
+
+    fn async_is_none_or<F, Fut>(self, op: F) -> AsyncIsNoneOr<T, F, Fut>
+    where
+        F: FnOnce(T) -> Fut,
+        Fut: Future<Output = bool>,
+    {
+        AsyncIsNoneOr::new(self, op)
+    }
+
+    fn async_ok_or_else<E, F, Fut>(self, err: F) -> AsyncOkOrElse<T, F, Fut>
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = E>,
+    {
+        AsyncOkOrElse::new(self, err)
+    }
+
+    fn async_zip_with<U, R, F, Fut>(self, other: Option<U>, op: F) -> AsyncZipWith<T, U, F, Fut>
+    where
+        F: FnOnce(T, U) -> Fut,
+        Fut: Future<Output = R>,
+    {
+        AsyncZipWith::new(self, other, op)
+    }
+
+    fn async_ok_or<E>(self, err: E) -> Ready<Result<T, E>> {
+        ready(self.ok_or(err))
+    }
+
+    fn async_inspect<F, Fut>(self, op: F) -> AsyncInspect<T, F, Fut>
+    where
+        F: FnOnce(&T) -> Fut,
+        Fut: Future<Output = ()>,
+    {
+        AsyncInspect::new(self, op)
+    }
+
+    fn async_take_if<'a, F, Fut>(&'a mut self, predicate: F) -> AsyncTakeIf<'a, T, F, Fut>
+    where
+        T: 'a,
+        F: FnOnce(&mut T) -> Fut,
+        Fut: Future<Output = bool>,
+    {
+        AsyncTakeIf::new(self, predicate)
+    }
+
+    fn async_or_default(self) -> Ready<T>
+    where
+        T: Default,
+    {
+        ready(self.unwrap_or_default())
+    }
+
+    fn async_xor<F, Fut>(self, other: Option<T>, tie_break: F) -> AsyncXor<T, F, Fut>
+    where
+        F: FnOnce(T, T) -> Fut,
+        Fut: Future<Output = Option<T>>,
+    {
+        AsyncXor::new(self, other, tie_break)
+    }
+}
+
+impl<T> AsyncFlattenExt for Option<Option<T>> {
+    type Output = Option<T>;
+
+    fn async_flatten(self) -> Ready<Option<T>> {
+        ready(self.flatten())
+    }
+}
+
+pin_project! {
+    /// Future for the [`async_map`](AsyncOptionExt::async_map) method.
+    #[must_use = "futures do nothing unless you `.await` or poll them"]
+    pub struct AsyncMap<T, F, Fut> {
+        #[pin]
+        state: State<(Option<T>, F), Fut, ()>,
+    }
+}
+
+impl<T, F, Fut> AsyncMap<T, F, Fut> {
+    fn new(this: Option<T>, op: F) -> Self {
+        Self {
+            state: State::new((this, op)),
+        }
+    }
+}
+
+impl<T, F, Fut> Future for AsyncMap<T, F, Fut>
+where
+    F: FnOnce(T) -> Fut,
+    Fut: Future,
+{
+    type Output = Option<Fut::Output>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        self.project().state.poll_with(
+            "async_map",
+            cx,
+            |(this, op)| match this {
+                Some(value) => Step::Run(op(value), ()),
+                None => Step::Ready(None),
+            },
+            |output, ()| Some(output),
+        )
+    }
+}
+
+pin_project! {
+    /// Future for the [`async_and_then`](AsyncOptionExt::async_and_then) method.
+    #[must_use = "futures do nothing unless you `.await` or poll them"]
+    pub struct AsyncAndThen<T, F, Fut> {
+        #[pin]
+        state: State<(Option<T>, F), Fut, ()>,
+    }
+}
+
+impl<T, F, Fut> AsyncAndThen<T, F, Fut> {
+    fn new(this: Option<T>, op: F) -> Self {
+        Self {
+            state: State::new((this, op)),
+        }
+    }
+}
+
+impl<T, U, F, Fut> Future for AsyncAndThen<T, F, Fut>
+where
+    F: FnOnce(T) -> Fut,
+    Fut: Future<Output = Option<U>>,
+{
+    type Output = Option<U>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        self.project().state.poll_with(
+            "async_and_then",
+            cx,
+            |(this, op)| match this {
+                Some(value) => Step::Run(op(value), ()),
+                None => Step::Ready(None),
+            },
+            |output, ()| output,
+        )
+    }
+}
+
+pin_project! {
+    /// Future for the [`async_unwrap_or_else`](AsyncOptionExt::async_unwrap_or_else) method.
+    #[must_use = "futures do nothing unless you `.await` or poll them"]
+    pub struct AsyncUnwrapOrElse<T, F, Fut> {
+        #[pin]
+        state: State<(Option<T>, F), Fut, ()>,
+    }
+}
+
+impl<T, F, Fut> AsyncUnwrapOrElse<T, F, Fut> {
+    fn new(this: Option<T>, op: F) -> Self {
+        Self {
+            state: State::new((this, op)),
+        }
+    }
+}
+
+impl<T, F, Fut> Future for AsyncUnwrapOrElse<T, F, Fut>
+where
+    F: FnOnce() -> Fut,
+    Fut: Future<Output = T>,
+{
+    type Output = T;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        self.project().state.poll_with(
+            "async_unwrap_or_else",
+            cx,
+            |(this, op)| match this {
+                Some(value) => Step::Ready(value),
+                None => Step::Run(op(), ()),
+            },
+            |output, ()| output,
+        )
+    }
+}
+
+pin_project! {
+    /// Future for the [`async_or_else`](AsyncOptionExt::async_or_else) method.
+    #[must_use = "futures do nothing unless you `.await` or poll them"]
+    pub struct AsyncOrElse<T, F, Fut> {
+        #[pin]
+        state: State<(Option<T>, F), Fut, ()>,
+    }
+}
+
+impl<T, F, Fut> AsyncOrElse<T, F, Fut> {
+    fn new(this: Option<T>, op: F) -> Self {
+        Self {
+            state: State::new((this, op)),
+        }
+    }
+}
+
+impl<T, F, Fut> Future for AsyncOrElse<T, F, Fut>
+where
+    F: FnOnce() -> Fut,
+    Fut: Future<Output = Option<T>>,
+{
+    type Output = Option<T>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        self.project().state.poll_with(
+            "async_or_else",
+            cx,
+            |(this, op)| match this {
+                Some(value) => Step::Ready(Some(value)),
+                None => Step::Run(op(), ()),
+            },
+            |output, ()| output,
+        )
+    }
+}
+
+pin_project! {
+    /// Future for the [`async_get_or_insert_with`](AsyncOptionExt::async_get_or_insert_with)
+    /// method.
+    #[must_use = "futures do nothing unless you `.await` or poll them"]
+    pub struct AsyncGetOrInsertWith<'a, T, F, Fut> {
+        #[pin]
+        state: State<(&'a mut Option<T>, F), Fut, &'a mut Option<T>>,
+    }
+}
+
+impl<'a, T, F, Fut> AsyncGetOrInsertWith<'a, T, F, Fut> {
+    fn new(this: &'a mut Option<T>, op: F) -> Self {
+        Self {
+            state: State::new((this, op)),
+        }
+    }
+}
+
+impl<'a, T, F, Fut> Future for AsyncGetOrInsertWith<'a, T, F, Fut>
+where
+    F: FnOnce() -> Fut,
+    Fut: Future<Output = T>,
+{
+    type Output = &'a mut T;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        self.project().state.poll_with(
+            "async_get_or_insert_with",
+            cx,
+            |(this, op)| match this {
+                Some(value) => Step::Ready(value),
+                None => Step::Run(op(), this),
+            },
+            |output, this| this.insert(output),
+        )
+    }
+}
+
+pin_project! {
+    /// Future for the [`async_filter`](AsyncOptionExt::async_filter) method.
+    #[must_use = "futures do nothing unless you `.await` or poll them"]
+    pub struct AsyncFilter<T, F, Fut> {
+        #[pin]
+        state: State<(Option<T>, F), Fut, T>,
+    }
+}
+
+impl<T, F, Fut> AsyncFilter<T, F, Fut> {
+    fn new(this: Option<T>, predicate: F) -> Self {
+        Self {
+            state: State::new((this, predicate)),
+        }
+    }
+}
+
+impl<T, F, Fut> Future for AsyncFilter<T, F, Fut>
+where
+    F: FnOnce(&T) -> Fut,
+    Fut: Future<Output = bool>,
+{
+    type Output = Option<T>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        self.project().state.poll_with(
+            "async_filter",
+            cx,
+            |(this, predicate)| match this {
+                Some(value) => Step::Run(predicate(&value), value),
+                None => Step::Ready(None),
+            },
+            |keep, value| keep.then_some(value),
+        )
+    }
+}
+
+pin_project! {
+    /// Future for the [`async_is_some_and`](AsyncOptionExt::async_is_some_and) method.
+    #[must_use = "futures do nothing unless you `.await` or poll them"]
+    pub struct AsyncIsSomeAnd<T, F, Fut> {
+        #[pin]
+        state: State<(Option<T>, F), Fut, ()>,
+    }
+}
+
+impl<T, F, Fut> AsyncIsSomeAnd<T, F, Fut> {
+    fn new(this: Option<T>, op: F) -> Self {
+        Self {
+            state: State::new((this, op)),
+        }
+    }
+}
+
+impl<T, F, Fut> Future for AsyncIsSomeAnd<T, F, Fut>
+where
+    F: FnOnce(T) -> Fut,
+    Fut: Future<Output = bool>,
+{
+    type Output = bool;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        self.project().state.poll_with(
+            "async_is_some_and",
+            cx,
+            |(this, op)| match this {
+                Some(value) => Step::Run(op(value), ()),
+                None => Step::Ready(false),
+            },
+            |output, ()| output,
+        )
+    }
+}
+
+pin_project! {
+    /// Future for the [`async_is_none_or`](AsyncOptionExt::async_is_none_or) method.
+    #[must_use = "futures do nothing unless you `.await` or poll them"]
+    pub struct AsyncIsNoneOr<T, F, Fut> {
+        #[pin]
+        state: State<(Option<T>, F), Fut, ()>,
+    }
+}
+
+impl<T, F, Fut> AsyncIsNoneOr<T, F, Fut> {
+    fn new(this: Option<T>, op: F) -> Self {
+        Self {
+            state: State::new((this, op)),
+        }
+    }
+}
+
+impl<T, F, Fut> Future for AsyncIsNoneOr<T, F, Fut>
+where
+    F: FnOnce(T) -> Fut,
+    Fut: Future<Output = bool>,
+{
+    type Output = bool;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        self.project().state.poll_with(
+            "async_is_none_or",
+            cx,
+            |(this, op)| match this {
+                Some(value) => Step::Run(op(value), ()),
+                None => Step::Ready(true),
+            },
+            |output, ()| output,
+        )
+    }
+}
+
+pin_project! {
+    /// Future for the [`async_ok_or_else`](AsyncOptionExt::async_ok_or_else) method.
+    #[must_use = "futures do nothing unless you `.await` or poll them"]
+    pub struct AsyncOkOrElse<T, F, Fut> {
+        #[pin]
+        state: State<(Option<T>, F), Fut, ()>,
+    }
+}
+
+impl<T, F, Fut> AsyncOkOrElse<T, F, Fut> {
+    fn new(this: Option<T>, err: F) -> Self {
+        Self {
+            state: State::new((this, err)),
+        }
+    }
+}
+
+impl<T, F, Fut> Future for AsyncOkOrElse<T, F, Fut>
+where
+    F: FnOnce() -> Fut,
+    Fut: Future,
+{
+    type Output = Result<T, Fut::Output>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        self.project().state.poll_with(
+            "async_ok_or_else",
+            cx,
+            |(this, err)| match this {
+                Some(value) => Step::Ready(Ok(value)),
+                None => Step::Run(err(), ()),
+            },
+            |output, ()| Err(output),
+        )
+    }
+}
+
+pin_project! {
+    /// Future for the [`async_zip_with`](AsyncOptionExt::async_zip_with) method.
+    #[must_use = "futures do nothing unless you `.await` or poll them"]
+    pub struct AsyncZipWith<T, U, F, Fut> {
+        #[pin]
+        state: State<(Option<T>, Option<U>, F), Fut, ()>,
+    }
+}
+
+impl<T, U, F, Fut> AsyncZipWith<T, U, F, Fut> {
+    fn new(this: Option<T>, other: Option<U>, op: F) -> Self {
+        Self {
+            state: State::new((this, other, op)),
+        }
+    }
+}
+
+impl<T, U, F, Fut> Future for AsyncZipWith<T, U, F, Fut>
+where
+    F: FnOnce(T, U) -> Fut,
+    Fut: Future,
+{
+    type Output = Option<Fut::Output>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        self.project().state.poll_with(
+            "async_zip_with",
+            cx,
+            |(this, other, op)| match (this, other) {
+                (Some(a), Some(b)) => Step::Run(op(a, b), ()),
+                _ => Step::Ready(None),
+            },
+            |output, ()| Some(output),
+        )
+    }
+}
+
+pin_project! {
+    /// Future for the [`async_inspect`](AsyncOptionExt::async_inspect) method.
+    #[must_use = "futures do nothing unless you `.await` or poll them"]
+    pub struct AsyncInspect<T, F, Fut> {
+        #[pin]
+        state: State<(Option<T>, F), Fut, T>,
+    }
+}
+
+impl<T, F, Fut> AsyncInspect<T, F, Fut> {
+    fn new(this: Option<T>, op: F) -> Self {
+        Self {
+            state: State::new((this, op)),
+        }
+    }
+}
+
+impl<T, F, Fut> Future for AsyncInspect<T, F, Fut>
+where
+    F: FnOnce(&T) -> Fut,
+    Fut: Future<Output = ()>,
+{
+    type Output = Option<T>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        self.project().state.poll_with(
+            "async_inspect",
+            cx,
+            |(this, op)| match this {
+                Some(value) => Step::Run(op(&value), value),
+                None => Step::Ready(None),
+            },
+            |(), value| Some(value),
+        )
+    }
+}
+
+pin_project! {
+    /// Future for the [`async_take_if`](AsyncOptionExt::async_take_if) method.
+    #[must_use = "futures do nothing unless you `.await` or poll them"]
+    pub struct AsyncTakeIf<'a, T, F, Fut> {
+        #[pin]
+        state: State<(&'a mut Option<T>, F), Fut, &'a mut Option<T>>,
+    }
+}
+
+impl<'a, T, F, Fut> AsyncTakeIf<'a, T, F, Fut> {
+    fn new(this: &'a mut Option<T>, predicate: F) -> Self {
+        Self {
+            state: State::new((this, predicate)),
+        }
+    }
+}
+
+impl<T, F, Fut> Future for AsyncTakeIf<'_, T, F, Fut>
+where
+    F: FnOnce(&mut T) -> Fut,
+    Fut: Future<Output = bool>,
+{
+    type Output = Option<T>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        self.project().state.poll_with(
+            "async_take_if",
+            cx,
+            |(this, predicate)| match this.as_mut() {
+                Some(value) => Step::Run(predicate(value), this),
+                None => Step::Ready(None),
+            },
+            |take, this| if take { this.take() } else { None },
+        )
+    }
+}
+
+pin_project! {
+    /// Future for the [`async_xor`](AsyncOptionExt::async_xor) method.
+    #[must_use = "futures do nothing unless you `.await` or poll them"]
+    pub struct AsyncXor<T, F, Fut> {
+        #[pin]
+        state: State<(Option<T>, Option<T>, F), Fut, ()>,
+    }
+}
+
+impl<T, F, Fut> AsyncXor<T, F, Fut> {
+    fn new(this: Option<T>, other: Option<T>, tie_break: F) -> Self {
+        Self {
+            state: State::new((this, other, tie_break)),
+        }
+    }
+}
+
+impl<T, F, Fut> Future for AsyncXor<T, F, Fut>
+where
+    F: FnOnce(T, T) -> Fut,
+    Fut: Future<Output = Option<T>>,
+{
+    type Output = Option<T>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        self.project().state.poll_with(
+            "async_xor",
+            cx,
+            |(this, other, tie_break)| match (this, other) {
+                (Some(a), Some(b)) => Step::Run(tie_break(a, b), ()),
+                (Some(value), None) | (None, Some(value)) => Step::Ready(Some(value)),
+                (None, None) => Step::Ready(None),
+            },
+            |output, ()| output,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::{never_polled, poll_once, yield_now};
+    use core::pin::pin;
+    use futures::executor::block_on;
+    use std::string::{String, ToString};
+    use std::vec::Vec;
+
+    #[test]
+    fn test_async_map() {
+        block_on(async {
+            let o: Option<i32> = Some(2);
+            let res = o.async_map(|v| async move { v * 3 }).await;
+            assert_eq!(res, Some(6));
+
+            let o: Option<i32> = None;
+            let res = o.async_map(|v| async move { v * 3 }).await;
+            assert_eq!(res, None);
+        });
+    }
+
+    #[test]
+    fn test_async_and_then() {
+        block_on(async {
+            let o: Option<i32> = Some(2);
+            let res = o.async_and_then(|v| async move { Some(v * 5) }).await;
+            assert_eq!(res, Some(10));
+
+            let o: Option<i32> = Some(2);
+            let res = o.async_and_then(|_| async move { None::<i32> }).await;
+            assert_eq!(res, None);
+
+            let o: Option<i32> = None;
+            let res = o.async_and_then(|v| async move { Some(v * 5) }).await;
+            assert_eq!(res, None);
+        });
+    }
+
+    #[test]
+    fn test_async_unwrap_or_else() {
+        block_on(async {
+            let o: Option<i32> = Some(2);
+            let res = o.async_unwrap_or_else(never_polled).await;
+            assert_eq!(res, 2);
+
+            let o: Option<i32> = None;
+            let res = o.async_unwrap_or_else(|| async { 7 }).await;
+            assert_eq!(res, 7);
+        });
+    }
+
+    #[test]
+    fn test_async_or_else() {
+        block_on(async {
+            let o: Option<i32> = Some(2);
+            let res = o.async_or_else(never_polled).await;
+            assert_eq!(res, Some(2));
+
+            let o: Option<i32> = None;
+            let res = o.async_or_else(|| async { Some(7) }).await;
+            assert_eq!(res, Some(7));
+
+            let o: Option<i32> = None;
+            let res = o.async_or_else(|| async { None }).await;
+            assert_eq!(res, None);
+        });
+    }
+
+    #[test]
+    fn test_async_get_or_insert_with() {
+        block_on(async {
+            let mut o: Option<i32> = Some(2);
+            let value = o.async_get_or_insert_with(never_polled).await;
+            *value += 1;
+            assert_eq!(o, Some(3));
+
+            let mut o: Option<i32> = None;
+            let value = o.async_get_or_insert_with(|| async { 7 }).await;
+            *value += 1;
+            assert_eq!(o, Some(8));
+        });
+    }
+
+    #[test]
+    fn test_async_filter() {
+        block_on(async {
+            let o: Option<i32> = None;
+            let res = o.async_filter(|_| never_polled()).await;
+            assert_eq!(res, None);
+
+            let o: Option<i32> = Some(4);
+            let res = o.async_filter(|&v| async move { v % 2 == 0 }).await;
+            assert_eq!(res, Some(4));
+
+            let o: Option<i32> = Some(3);
+            let res = o.async_filter(|&v| async move { v % 2 == 0 }).await;
+            assert_eq!(res, None);
+        });
+    }
+
+    #[test]
+    fn test_async_is_some_and() {
+        block_on(async {
+            let o: Option<i32> = Some(4);
+            assert!(o.async_is_some_and(|v| async move { v % 2 == 0 }).await);
+
+            let o: Option<i32> = Some(3);
+            assert!(!o.async_is_some_and(|v| async move { v % 2 == 0 }).await);
+
+            let o: Option<i32> = None;
+            assert!(!o.async_is_some_and(|_| never_polled()).await);
+        });
+    }
+
+    #[test]
+    fn test_async_is_none_or() {
+        block_on(async {
+            let o: Option<i32> = Some(4);
+            assert!(o.async_is_none_or(|v| async move { v % 2 == 0 }).await);
+
+            let o: Option<i32> = Some(3);
+            assert!(!o.async_is_none_or(|v| async move { v % 2 == 0 }).await);
+
+            let o: Option<i32> = None;
+            assert!(o.async_is_none_or(|_| never_polled()).await);
+        });
+    }
+
+    #[test]
+    fn test_async_ok_or_else() {
+        block_on(async {
+            let o: Option<i32> = Some(2);
+            let res: Result<i32, &str> = o.async_ok_or_else(never_polled).await;
+            assert_eq!(res, Ok(2));
+
+            let o: Option<i32> = None;
+            let res = o.async_ok_or_else(|| async { "missing" }).await;
+            assert_eq!(res, Err("missing"));
+        });
+    }
+
+    #[test]
+    fn test_async_zip_with() {
+        block_on(async {
+            let res = Some(2)
+                .async_zip_with(Some(3), |a, b| async move { a * b })
+                .await;
+            assert_eq!(res, Some(6));
+
+            let res: Option<i32> = Some(2)
+                .async_zip_with(None::<i32>, |_, _| never_polled())
+                .await;
+            assert_eq!(res, None);
+
+            let res: Option<i32> = None::<i32>
+                .async_zip_with(Some(3), |_, _| never_polled())
+                .await;
+            assert_eq!(res, None);
+
+            let res: Option<i32> = None::<i32>
+                .async_zip_with(None::<i32>, |_, _| never_polled())
+                .await;
+            assert_eq!(res, None);
+        });
+    }
+
+    #[test]
+    fn test_async_ok_or() {
+        block_on(async {
+            let o: Option<i32> = Some(3);
+            assert_eq!(o.async_ok_or("missing").await, Ok(3));
+
+            let o: Option<i32> = None;
+            assert_eq!(o.async_ok_or("missing").await, Err("missing"));
+        });
+    }
+
+    #[test]
+    fn test_async_inspect() {
+        block_on(async {
+            let mut seen = None;
+            let o: Option<i32> = Some(4);
+            let res = o
+                .async_inspect(|v| {
+                    seen = Some(*v);
+                    async {}
+                })
+                .await;
+            assert_eq!(res, Some(4));
+            assert_eq!(seen, Some(4));
+
+            let o: Option<i32> = None;
+            assert_eq!(o.async_inspect(|_| never_polled()).await, None);
+        });
+    }
+
+    #[test]
+    fn test_async_flatten() {
+        block_on(async {
+            assert_eq!(Some(Some(1)).async_flatten().await, Some(1));
+            assert_eq!(Some(None::<i32>).async_flatten().await, None);
+            assert_eq!(None::<Option<i32>>.async_flatten().await, None);
+
+            let res = Some(2)
+                .async_map(|v| async move { (v > 0).then_some(v) })
+                .await
+                .async_flatten()
+                .await;
+            assert_eq!(res, Some(2));
+        });
+    }
+
+    #[test]
+    fn test_async_take_if() {
+        block_on(async {
+            let mut o = Some(4);
+            let taken = o.async_take_if(|v| {
+                let even = *v % 2 == 0;
+                async move { even }
+            });
+            assert_eq!(taken.await, Some(4));
+            assert_eq!(o, None);
+
+            let mut o = Some(3);
+            let taken = o
+                .async_take_if(|v| {
+                    *v += 10;
+                    async { false }
+                })
+                .await;
+            assert_eq!(taken, None);
+            assert_eq!(o, Some(13));
+
+            let mut o: Option<i32> = None;
+            assert_eq!(o.async_take_if(|_| never_polled()).await, None);
+        });
+    }
+
+    #[test]
+    fn test_cancel_async_get_or_insert_with_leaves_none() {
+        let mut o: Option<i32> = None;
+        {
+            let mut fut = pin!(o.async_get_or_insert_with(|| async {
+                yield_now().await;
+                1
+            }));
+            assert!(poll_once(fut.as_mut()).is_pending());
+        }
+        assert_eq!(o, None);
+    }
+
+    #[test]
+    fn test_cancel_async_take_if_keeps_value() {
+        let mut o = Some(1);
+        {
+            let mut fut = pin!(o.async_take_if(|v| {
+                *v += 1;
+                async {
+                    yield_now().await;
+                    true
+                }
+            }));
+            assert!(poll_once(fut.as_mut()).is_pending());
+        }
+        assert_eq!(o, Some(2));
+    }
+
+    #[test]
+    fn test_async_or_default() {
+        block_on(async {
+            assert_eq!(Some(5).async_or_default().await, 5);
+            assert_eq!(None::<i32>.async_or_default().await, 0);
+
+            let o: Option<String> = Some("set".to_string());
+            assert_eq!(o.async_or_default().await, "set");
+            assert_eq!(None::<String>.async_or_default().await, "");
+            assert_eq!(None::<Vec<u8>>.async_or_default().await, Vec::<u8>::new());
+        });
+    }
+
+    #[test]
+    fn test_async_xor() {
+        block_on(async {
+            let res = Some(1).async_xor(None, |_, _| never_polled()).await;
+            assert_eq!(res, Some(1));
+
+            let res = None.async_xor(Some(2), |_, _| never_polled()).await;
+            assert_eq!(res, Some(2));
+
+            let res = None::<i32>.async_xor(None, |_, _| never_polled()).await;
+            assert_eq!(res, None);
+
+            let mut conflict = None;
+            let res = Some(1)
+                .async_xor(Some(2), |a, b| {
+                    conflict = Some((a, b));
+                    async { None }
+                })
+                .await;
+            assert_eq!(res, None);
+            assert_eq!(conflict, Some((1, 2)));
+
+            let res = Some(1)
+                .async_xor(Some(2), |a, b| async move {
+                    yield_now().await;
+                    Some(a.max(b))
+                })
+                .await;
+            assert_eq!(res, Some(2));
+        });
+    }
+}
@@ -0,0 +1,203 @@
+//! Fluent multi-step chains over a result.
+//!
+//! A [`Pipeline`] records each step as it is added and only runs them once the
+//! future returned by [`run`](Pipeline::run) is awaited, so a long chain needs a
+//! single `.await`. Steps are composed statically, each wrapping the future of
+//! the previous ones, so no allocation is involved.
+
+use core::future::{Future, Ready, ready};
+
+use crate::AsyncResultExt;
+
+/// A lazily built chain of async steps over a `Result<T, E>`.
+///
+/// `Fut` is the future running every step added so far. Steps run in order once
+/// the pipeline is [`run`](Pipeline::run), and short-circuit like the
+/// [`AsyncResultExt`] method each one mirrors: after an `Err`, the closures of
+/// the `Ok` steps are dropped without being called.
+///
+/// ```
+/// use async_result_ext::pipeline::Pipeline;
+///
+/// # futures::executor::block_on(async {
+/// let input: Result<&str, String> = Ok("21");
+/// let res = Pipeline::new(input)
+///     .and_then(|s| async move { s.parse::<i32>().map_err(|e| e.to_string()) })
+///     .map(|n| async move { n * 2 })
+///     .inspect(|n| {
+///         let n = *n;
+///         async move { assert_eq!(n, 42) }
+///     })
+///     .map_err(|e| async move { format!("invalid input: {e}") })
+///     .run()
+///     .await;
+/// assert_eq!(res, Ok(42));
+/// # });
+/// ```
+#[must_use = "pipelines do nothing unless you `.run().await` them"]
+pub struct Pipeline<Fut> {
+    fut: Fut,
+}
+
+impl<T, E> Pipeline<Ready<Result<T, E>>> {
+    /// Starts a pipeline from a result.
+    pub fn new(result: Result<T, E>) -> Self {
+        Self { fut: ready(result) }
+    }
+}
+
+impl<Fut> Pipeline<Fut> {
+    /// Starts a pipeline from a future producing the initial result.
+    pub fn from_future(fut: Fut) -> Self {
+        Self { fut }
+    }
+
+    /// Returns the future running every step in order.
+    pub fn run(self) -> Fut {
+        self.fut
+    }
+}
+
+impl<T, E, Fut> Pipeline<Fut>
+where
+    Fut: Future<Output = Result<T, E>>,
+{
+    /// Adds a step that maps the `Ok` value, as
+    /// [`async_map`](AsyncResultExt::async_map) does.
+    pub fn map<U, F, StepFut>(self, op: F) -> Pipeline<impl Future<Output = Result<U, E>>>
+    where
+        F: FnOnce(T) -> StepFut,
+        StepFut: Future<Output = U>,
+    {
+        let fut = self.fut;
+        Pipeline::from_future(async move { fut.await.async_map(op).await })
+    }
+
+    /// Adds a fallible step on the `Ok` value, as
+    /// [`async_and_then`](AsyncResultExt::async_and_then) does.
+    pub fn and_then<U, F, StepFut>(self, op: F) -> Pipeline<impl Future<Output = Result<U, E>>>
+    where
+        F: FnOnce(T) -> StepFut,
+        StepFut: Future<Output = Result<U, E>>,
+    {
+        let fut = self.fut;
+        Pipeline::from_future(async move { fut.await.async_and_then(op).await })
+    }
+
+    /// Adds a step that maps the `Err` value, as
+    /// [`async_map_err`](AsyncResultExt::async_map_err) does.
+    pub fn map_err<O, F, StepFut>(self, op: F) -> Pipeline<impl Future<Output = Result<T, O>>>
+    where
+        F: FnOnce(E) -> StepFut,
+        StepFut: Future<Output = O>,
+    {
+        let fut = self.fut;
+        Pipeline::from_future(async move { fut.await.async_map_err(op).await })
+    }
+
+    /// Adds a step that recovers from an `Err`, as
+    /// [`async_or_else`](AsyncResultExt::async_or_else) does.
+    pub fn or_else<O, F, StepFut>(self, op: F) -> Pipeline<impl Future<Output = Result<T, O>>>
+    where
+        F: FnOnce(E) -> StepFut,
+        StepFut: Future<Output = Result<T, O>>,
+    {
+        let fut = self.fut;
+        Pipeline::from_future(async move { fut.await.async_or_else(op).await })
+    }
+
+    /// Adds a step that observes the `Ok` value, as
+    /// [`async_inspect`](AsyncResultExt::async_inspect) does.
+    pub fn inspect<F, StepFut>(self, op: F) -> Pipeline<impl Future<Output = Result<T, E>>>
+    where
+        F: FnOnce(&T) -> StepFut,
+        StepFut: Future<Output = ()>,
+    {
+        let fut = self.fut;
+        Pipeline::from_future(async move { fut.await.async_inspect(op).await })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::{never_polled, yield_now};
+    use core::cell::RefCell;
+    use futures::executor::block_on;
+    use std::format;
+    use std::string::{String, ToString};
+    use std::vec::Vec;
+
+    #[test]
+    fn test_pipeline_runs_steps_in_order() {
+        let order = RefCell::new(Vec::new());
+        block_on(async {
+            let res: Result<String, &str> = Pipeline::new(Ok(1))
+                .map(|v| {
+                    order.borrow_mut().push("map");
+                    async move {
+                        yield_now().await;
+                        v + 1
+                    }
+                })
+                .and_then(|v| {
+                    order.borrow_mut().push("and_then");
+                    async move { Ok(v * 10) }
+                })
+                .map_err(|_: &str| never_polled::<&str>())
+                .or_else(|_| never_polled())
+                .map(|v| {
+                    order.borrow_mut().push("final map");
+                    async move { v.to_string() }
+                })
+                .run()
+                .await;
+            assert_eq!(res.as_deref(), Ok("20"));
+        });
+        assert_eq!(order.into_inner(), ["map", "and_then", "final map"]);
+    }
+
+    #[test]
+    fn test_pipeline_short_circuits() {
+        block_on(async {
+            let mut failed_at = None;
+            let res = Pipeline::new(Ok::<_, String>(3))
+                .and_then(|v| async move {
+                    if v > 2 {
+                        Err(format!("{v} too large"))
+                    } else {
+                        Ok(v)
+                    }
+                })
+                .map(|_| never_polled::<i32>())
+                .and_then(|_| never_polled::<Result<u8, String>>())
+                .inspect(|_| never_polled())
+                .map_err(|e| {
+                    failed_at = Some(e.clone());
+                    async move { e.len() }
+                })
+                .run()
+                .await;
+            assert_eq!(res, Err(11));
+            assert_eq!(failed_at.as_deref(), Some("3 too large"));
+        });
+    }
+
+    #[test]
+    fn test_pipeline_is_lazy() {
+        let mut called = false;
+        let pipeline = Pipeline::new(Ok::<_, ()>(1)).map(|v| {
+            called = true;
+            async move { v }
+        });
+        drop(pipeline);
+        assert!(!called);
+
+        let recovered = block_on(
+            Pipeline::from_future(async { Err::<i32, _>("fail") })
+                .or_else(|_| async { Ok::<_, ()>(0) })
+                .run(),
+        );
+        assert_eq!(recovered, Ok(0));
+    }
+}
@@ -0,0 +1,33 @@
+//! Glob-importable re-exports of every extension trait.
+//!
+//! Traits behind a feature (`anyhow`, `stream`, `tracing`, `tokio`) are re-exported only
+//! when that feature is enabled.
+//!
+//! ```
+//! use async_result_ext::prelude::*;
+//!
+//! # futures::executor::block_on(async {
+//! let r: Result<i32, &str> = Ok(2);
+//! assert_eq!(r.async_map(|v| async move { v * 2 }).await, Ok(4));
+//!
+//! let o = Some(3);
+//! assert_eq!(o.async_ok_or("missing").await, Ok(3));
+//!
+//! let nested: Result<Result<i32, &str>, &str> = Ok(Ok(1));
+//! assert_eq!(nested.async_flatten().await, Ok(1));
+//! # });
+//! ```
+
+#[cfg(feature = "anyhow")]
+pub use crate::anyhow::AsyncResultAnyhowExt;
+pub use crate::option::AsyncOptionExt;
+#[cfg(feature = "tokio")]
+pub use crate::spawn::AsyncResultSpawnExt;
+#[cfg(feature = "stream")]
+pub use crate::stream::AsyncTryStreamExt;
+#[cfg(feature = "tracing")]
+pub use crate::trace::AsyncResultTraceExt;
+pub use crate::{
+    AsyncClonedExt, AsyncFlattenErrExt, AsyncFlattenExt, AsyncResultExt, AsyncResultRefExt,
+    AsyncTransposeExt, AsyncUnzipExt,
+};
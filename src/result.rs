@@ -0,0 +1,1613 @@
+//! Named futures returned by [`AsyncResultExt`](crate::AsyncResultExt),
+//! [`AsyncResultRefExt`](crate::AsyncResultRefExt) and
+//! [`AsyncFlattenErrExt`](crate::AsyncFlattenErrExt).
+//!
+//! Each combinator returns its own future type, so the futures can be stored in
+//! struct fields or named in `where` clauses. Callers who just `.await` never
+//! need to mention them.
+
+use core::fmt;
+use core::future::Future;
+use core::marker::PhantomData;
+use core::ops::Sub;
+use core::pin::Pin;
+use core::task::{Context, Poll, ready};
+use core::time::Duration;
+
+use pin_project_lite::pin_project;
+
+use crate::state::{Either, EitherOutput, State, Step, polled_after_completion};
+use crate::timeout::{Timeout, timeout};
+
+pin_project! {
+    /// Future for the [`async_map`](crate::AsyncResultExt::async_map) method.
+    #[must_use = "futures do nothing unless you `.await` or poll them"]
+    pub struct AsyncMap<T, E, F, Fut> {
+        #[pin]
+        state: State<(Result<T, E>, F), Fut, ()>,
+    }
+}
+
+impl<T, E, F, Fut> AsyncMap<T, E, F, Fut> {
+    pub(crate) fn new(this: Result<T, E>, op: F) -> Self {
+        Self {
+            state: State::new((this, op)),
+        }
+    }
+}
+
+impl<T, E, F, Fut> Future for AsyncMap<T, E, F, Fut>
+where
+    F: FnOnce(T) -> Fut,
+    Fut: Future,
+{
+    type Output = Result<Fut::Output, E>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        self.project().state.poll_with(
+            "async_map",
+            cx,
+            |(this, op)| match this {
+                Ok(value) => Step::Run(op(value), ()),
+                Err(err) => Step::Ready(Err(err)),
+            },
+            |output, ()| Ok(output),
+        )
+    }
+}
+
+pin_project! {
+    /// Future for the [`async_and_then`](crate::AsyncResultExt::async_and_then) method.
+    #[must_use = "futures do nothing unless you `.await` or poll them"]
+    pub struct AsyncAndThen<T, E, F, Fut> {
+        #[pin]
+        state: State<(Result<T, E>, F), Fut, ()>,
+    }
+}
+
+impl<T, E, F, Fut> AsyncAndThen<T, E, F, Fut> {
+    pub(crate) fn new(this: Result<T, E>, op: F) -> Self {
+        Self {
+            state: State::new((this, op)),
+        }
+    }
+}
+
+impl<T, E, U, F, Fut> Future for AsyncAndThen<T, E, F, Fut>
+where
+    F: FnOnce(T) -> Fut,
+    Fut: Future<Output = Result<U, E>>,
+{
+    type Output = Result<U, E>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        self.project().state.poll_with(
+            "async_and_then",
+            cx,
+            |(this, op)| match this {
+                Ok(value) => Step::Run(op(value), ()),
+                Err(err) => Step::Ready(Err(err)),
+            },
+            |output, ()| output,
+        )
+    }
+}
+
+pin_project! {
+    /// Future for the [`async_map_or`](crate::AsyncResultExt::async_map_or) method.
+    #[must_use = "futures do nothing unless you `.await` or poll them"]
+    pub struct AsyncMapOr<T, E, U, F, Fut> {
+        #[pin]
+        state: State<(Result<T, E>, U, F), Fut, ()>,
+    }
+}
+
+impl<T, E, U, F, Fut> AsyncMapOr<T, E, U, F, Fut> {
+    pub(crate) fn new(this: Result<T, E>, default: U, op: F) -> Self {
+        Self {
+            state: State::new((this, default, op)),
+        }
+    }
+}
+
+impl<T, E, U, F, Fut> Future for AsyncMapOr<T, E, U, F, Fut>
+where
+    F: FnOnce(T) -> Fut,
+    Fut: Future<Output = U>,
+{
+    type Output = U;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        self.project().state.poll_with(
+            "async_map_or",
+            cx,
+            |(this, default, op)| match this {
+                Ok(value) => Step::Run(op(value), ()),
+                Err(_) => Step::Ready(default),
+            },
+            |output, ()| output,
+        )
+    }
+}
+
+pin_project! {
+    /// Future for the [`async_map_or_else`](crate::AsyncResultExt::async_map_or_else) method.
+    #[must_use = "futures do nothing unless you `.await` or poll them"]
+    pub struct AsyncMapOrElse<T, E, D, F, Fut, DefFut> {
+        #[pin]
+        state: State<(Result<T, E>, D, F), Either<Fut, DefFut>, ()>,
+    }
+}
+
+impl<T, E, D, F, Fut, DefFut> AsyncMapOrElse<T, E, D, F, Fut, DefFut> {
+    pub(crate) fn new(this: Result<T, E>, default: D, op: F) -> Self {
+        Self {
+            state: State::new((this, default, op)),
+        }
+    }
+}
+
+impl<T, E, U, D, F, Fut, DefFut> Future for AsyncMapOrElse<T, E, D, F, Fut, DefFut>
+where
+    D: FnOnce(E) -> DefFut,
+    F: FnOnce(T) -> Fut,
+    DefFut: Future<Output = U>,
+    Fut: Future<Output = U>,
+{
+    type Output = U;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        self.project().state.poll_with(
+            "async_map_or_else",
+            cx,
+            |(this, default, op)| match this {
+                Ok(value) => Step::Run(Either::Left { fut: op(value) }, ()),
+                Err(err) => Step::Run(Either::Right { fut: default(err) }, ()),
+            },
+            |output, ()| match output {
+                EitherOutput::Left(output) | EitherOutput::Right(output) => output,
+            },
+        )
+    }
+}
+
+pin_project! {
+    /// Future for the [`async_map_err`](crate::AsyncResultExt::async_map_err) method.
+    #[must_use = "futures do nothing unless you `.await` or poll them"]
+    pub struct AsyncMapErr<T, E, F, Fut> {
+        #[pin]
+        state: State<(Result<T, E>, F), Fut, ()>,
+    }
+}
+
+impl<T, E, F, Fut> AsyncMapErr<T, E, F, Fut> {
+    pub(crate) fn new(this: Result<T, E>, op: F) -> Self {
+        Self {
+            state: State::new((this, op)),
+        }
+    }
+}
+
+impl<T, E, F, Fut> Future for AsyncMapErr<T, E, F, Fut>
+where
+    F: FnOnce(E) -> Fut,
+    Fut: Future,
+{
+    type Output = Result<T, Fut::Output>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        self.project().state.poll_with(
+            "async_map_err",
+            cx,
+            |(this, op)| match this {
+                Ok(value) => Step::Ready(Ok(value)),
+                Err(err) => Step::Run(op(err), ()),
+            },
+            |output, ()| Err(output),
+        )
+    }
+}
+
+pin_project! {
+    /// Future for the [`async_inspect`](crate::AsyncResultExt::async_inspect) method.
+    #[must_use = "futures do nothing unless you `.await` or poll them"]
+    pub struct AsyncInspect<T, E, F, Fut> {
+        #[pin]
+        state: State<(Result<T, E>, F), Fut, T>,
+    }
+}
+
+impl<T, E, F, Fut> AsyncInspect<T, E, F, Fut> {
+    pub(crate) fn new(this: Result<T, E>, op: F) -> Self {
+        Self {
+            state: State::new((this, op)),
+        }
+    }
+}
+
+impl<T, E, F, Fut> Future for AsyncInspect<T, E, F, Fut>
+where
+    F: FnOnce(&T) -> Fut,
+    Fut: Future<Output = ()>,
+{
+    type Output = Result<T, E>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        self.project().state.poll_with(
+            "async_inspect",
+            cx,
+            |(this, op)| match this {
+                Ok(value) => Step::Run(op(&value), value),
+                Err(err) => Step::Ready(Err(err)),
+            },
+            |(), value| Ok(value),
+        )
+    }
+}
+
+pin_project! {
+    /// Future for the [`async_inspect_err`](crate::AsyncResultExt::async_inspect_err) method.
+    #[must_use = "futures do nothing unless you `.await` or poll them"]
+    pub struct AsyncInspectErr<T, E, F, Fut> {
+        #[pin]
+        state: State<(Result<T, E>, F), Fut, E>,
+    }
+}
+
+impl<T, E, F, Fut> AsyncInspectErr<T, E, F, Fut> {
+    pub(crate) fn new(this: Result<T, E>, op: F) -> Self {
+        Self {
+            state: State::new((this, op)),
+        }
+    }
+}
+
+impl<T, E, F, Fut> Future for AsyncInspectErr<T, E, F, Fut>
+where
+    F: FnOnce(&E) -> Fut,
+    Fut: Future<Output = ()>,
+{
+    type Output = Result<T, E>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        self.project().state.poll_with(
+            "async_inspect_err",
+            cx,
+            |(this, op)| match this {
+                Ok(value) => Step::Ready(Ok(value)),
+                Err(err) => Step::Run(op(&err), err),
+            },
+            |(), err| Err(err),
+        )
+    }
+}
+
+pin_project! {
+    /// Future for the [`async_is_ok_and`](crate::AsyncResultExt::async_is_ok_and) method.
+    #[must_use = "futures do nothing unless you `.await` or poll them"]
+    pub struct AsyncIsOkAnd<T, E, F, Fut> {
+        #[pin]
+        state: State<(Result<T, E>, F), Fut, ()>,
+    }
+}
+
+impl<T, E, F, Fut> AsyncIsOkAnd<T, E, F, Fut> {
+    pub(crate) fn new(this: Result<T, E>, op: F) -> Self {
+        Self {
+            state: State::new((this, op)),
+        }
+    }
+}
+
+impl<T, E, F, Fut> Future for AsyncIsOkAnd<T, E, F, Fut>
+where
+    F: FnOnce(T) -> Fut,
+    Fut: Future<Output = bool>,
+{
+    type Output = bool;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        self.project().state.poll_with(
+            "async_is_ok_and",
+            cx,
+            |(this, op)| match this {
+                Ok(value) => Step::Run(op(value), ()),
+                Err(_) => Step::Ready(false),
+            },
+            |output, ()| output,
+        )
+    }
+}
+
+pin_project! {
+    /// Future for the [`async_is_err_and`](crate::AsyncResultExt::async_is_err_and) method.
+    #[must_use = "futures do nothing unless you `.await` or poll them"]
+    pub struct AsyncIsErrAnd<T, E, F, Fut> {
+        #[pin]
+        state: State<(Result<T, E>, F), Fut, ()>,
+    }
+}
+
+impl<T, E, F, Fut> AsyncIsErrAnd<T, E, F, Fut> {
+    pub(crate) fn new(this: Result<T, E>, op: F) -> Self {
+        Self {
+            state: State::new((this, op)),
+        }
+    }
+}
+
+impl<T, E, F, Fut> Future for AsyncIsErrAnd<T, E, F, Fut>
+where
+    F: FnOnce(E) -> Fut,
+    Fut: Future<Output = bool>,
+{
+    type Output = bool;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        self.project().state.poll_with(
+            "async_is_err_and",
+            cx,
+            |(this, op)| match this {
+                Ok(_) => Step::Ready(false),
+                Err(err) => Step::Run(op(err), ()),
+            },
+            |output, ()| output,
+        )
+    }
+}
+
+pin_project! {
+    /// Future for the [`async_unwrap_or_else`](crate::AsyncResultExt::async_unwrap_or_else)
+    /// and [`async_recover`](crate::AsyncResultExt::async_recover) methods.
+    #[must_use = "futures do nothing unless you `.await` or poll them"]
+    pub struct AsyncUnwrapOrElse<T, E, F, Fut> {
+        #[pin]
+        state: State<(Result<T, E>, F), Fut, ()>,
+    }
+}
+
+impl<T, E, F, Fut> AsyncUnwrapOrElse<T, E, F, Fut> {
+    pub(crate) fn new(this: Result<T, E>, op: F) -> Self {
+        Self {
+            state: State::new((this, op)),
+        }
+    }
+}
+
+impl<T, E, F, Fut> Future for AsyncUnwrapOrElse<T, E, F, Fut>
+where
+    F: FnOnce(E) -> Fut,
+    Fut: Future<Output = T>,
+{
+    type Output = T;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        self.project().state.poll_with(
+            "async_unwrap_or_else",
+            cx,
+            |(this, op)| match this {
+                Ok(value) => Step::Ready(value),
+                Err(err) => Step::Run(op(err), ()),
+            },
+            |output, ()| output,
+        )
+    }
+}
+
+pin_project! {
+    /// Future for the [`async_or_else`](crate::AsyncResultExt::async_or_else) method.
+    #[must_use = "futures do nothing unless you `.await` or poll them"]
+    pub struct AsyncOrElse<T, E, F, Fut> {
+        #[pin]
+        state: State<(Result<T, E>, F), Fut, ()>,
+    }
+}
+
+impl<T, E, F, Fut> AsyncOrElse<T, E, F, Fut> {
+    pub(crate) fn new(this: Result<T, E>, op: F) -> Self {
+        Self {
+            state: State::new((this, op)),
+        }
+    }
+}
+
+impl<T, E, O, F, Fut> Future for AsyncOrElse<T, E, F, Fut>
+where
+    F: FnOnce(E) -> Fut,
+    Fut: Future<Output = Result<T, O>>,
+{
+    type Output = Result<T, O>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        self.project().state.poll_with(
+            "async_or_else",
+            cx,
+            |(this, op)| match this {
+                Ok(value) => Step::Ready(Ok(value)),
+                Err(err) => Step::Run(op(err), ()),
+            },
+            |output, ()| output,
+        )
+    }
+}
+
+pin_project! {
+    /// Future for the [`async_and_then_into`](crate::AsyncResultExt::async_and_then_into) method.
+    #[must_use = "futures do nothing unless you `.await` or poll them"]
+    pub struct AsyncAndThenInto<T, E, F, Fut> {
+        #[pin]
+        state: State<(Result<T, E>, F), Fut, ()>,
+    }
+}
+
+impl<T, E, F, Fut> AsyncAndThenInto<T, E, F, Fut> {
+    pub(crate) fn new(this: Result<T, E>, op: F) -> Self {
+        Self {
+            state: State::new((this, op)),
+        }
+    }
+}
+
+impl<T, E, U, O, F, Fut> Future for AsyncAndThenInto<T, E, F, Fut>
+where
+    O: From<E>,
+    F: FnOnce(T) -> Fut,
+    Fut: Future<Output = Result<U, O>>,
+{
+    type Output = Result<U, O>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        self.project().state.poll_with(
+            "async_and_then_into",
+            cx,
+            |(this, op)| match this {
+                Ok(value) => Step::Run(op(value), ()),
+                Err(err) => Step::Ready(Err(O::from(err))),
+            },
+            |output, ()| output,
+        )
+    }
+}
+
+pin_project! {
+    /// Future for the [`async_map_err_into`](crate::AsyncResultExt::async_map_err_into) method.
+    #[must_use = "futures do nothing unless you `.await` or poll them"]
+    pub struct AsyncMapErrInto<T, E, O, F, Fut> {
+        #[pin]
+        state: State<(Result<T, E>, F), Fut, PhantomData<fn() -> O>>,
+    }
+}
+
+impl<T, E, O, F, Fut> AsyncMapErrInto<T, E, O, F, Fut> {
+    pub(crate) fn new(this: Result<T, E>, op: F) -> Self {
+        Self {
+            state: State::new((this, op)),
+        }
+    }
+}
+
+impl<T, E, O, M, F, Fut> Future for AsyncMapErrInto<T, E, O, F, Fut>
+where
+    O: From<M>,
+    F: FnOnce(E) -> Fut,
+    Fut: Future<Output = M>,
+{
+    type Output = Result<T, O>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        self.project().state.poll_with(
+            "async_map_err_into",
+            cx,
+            |(this, op)| match this {
+                Ok(value) => Step::Ready(Ok(value)),
+                Err(err) => Step::Run(op(err), PhantomData),
+            },
+            |output, PhantomData| Err(O::from(output)),
+        )
+    }
+}
+
+pin_project! {
+    /// Future for the [`async_map_both`](crate::AsyncResultExt::async_map_both) method.
+    #[must_use = "futures do nothing unless you `.await` or poll them"]
+    pub struct AsyncMapBoth<T, E, F, G, FFut, GFut> {
+        #[pin]
+        state: State<(Result<T, E>, F, G), Either<FFut, GFut>, ()>,
+    }
+}
+
+impl<T, E, F, G, FFut, GFut> AsyncMapBoth<T, E, F, G, FFut, GFut> {
+    pub(crate) fn new(this: Result<T, E>, ok_op: F, err_op: G) -> Self {
+        Self {
+            state: State::new((this, ok_op, err_op)),
+        }
+    }
+}
+
+impl<T, E, F, G, FFut, GFut> Future for AsyncMapBoth<T, E, F, G, FFut, GFut>
+where
+    F: FnOnce(T) -> FFut,
+    G: FnOnce(E) -> GFut,
+    FFut: Future,
+    GFut: Future,
+{
+    type Output = Result<FFut::Output, GFut::Output>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        self.project().state.poll_with(
+            "async_map_both",
+            cx,
+            |(this, ok_op, err_op)| match this {
+                Ok(value) => Step::Run(Either::Left { fut: ok_op(value) }, ()),
+                Err(err) => Step::Run(Either::Right { fut: err_op(err) }, ()),
+            },
+            |output, ()| match output {
+                EitherOutput::Left(value) => Ok(value),
+                EitherOutput::Right(err) => Err(err),
+            },
+        )
+    }
+}
+
+pin_project! {
+    /// Future for the [`async_and`](crate::AsyncResultExt::async_and) method.
+    #[must_use = "futures do nothing unless you `.await` or poll them"]
+    pub struct AsyncAnd<T, E, Fut> {
+        #[pin]
+        state: State<(Result<T, E>, Fut), Fut, ()>,
+    }
+}
+
+impl<T, E, Fut> AsyncAnd<T, E, Fut> {
+    pub(crate) fn new(this: Result<T, E>, fut: Fut) -> Self {
+        Self {
+            state: State::new((this, fut)),
+        }
+    }
+}
+
+impl<T, E, U, Fut> Future for AsyncAnd<T, E, Fut>
+where
+    Fut: Future<Output = Result<U, E>>,
+{
+    type Output = Result<U, E>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        self.project().state.poll_with(
+            "async_and",
+            cx,
+            |(this, fut)| match this {
+                Ok(_) => Step::Run(fut, ()),
+                Err(err) => Step::Ready(Err(err)),
+            },
+            |output, ()| output,
+        )
+    }
+}
+
+pin_project! {
+    /// Future for the [`async_or`](crate::AsyncResultExt::async_or) method.
+    #[must_use = "futures do nothing unless you `.await` or poll them"]
+    pub struct AsyncOr<T, E, Fut> {
+        #[pin]
+        state: State<(Result<T, E>, Fut), Fut, ()>,
+    }
+}
+
+impl<T, E, Fut> AsyncOr<T, E, Fut> {
+    pub(crate) fn new(this: Result<T, E>, fut: Fut) -> Self {
+        Self {
+            state: State::new((this, fut)),
+        }
+    }
+}
+
+impl<T, E, O, Fut> Future for AsyncOr<T, E, Fut>
+where
+    Fut: Future<Output = Result<T, O>>,
+{
+    type Output = Result<T, O>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        self.project().state.poll_with(
+            "async_or",
+            cx,
+            |(this, fut)| match this {
+                Ok(value) => Step::Ready(Ok(value)),
+                Err(_) => Step::Run(fut, ()),
+            },
+            |output, ()| output,
+        )
+    }
+}
+
+pin_project! {
+    /// Future for the
+    /// [`async_map_or_else_concurrent`](crate::AsyncResultExt::async_map_or_else_concurrent)
+    /// method.
+    #[must_use = "futures do nothing unless you `.await` or poll them"]
+    pub struct AsyncMapOrElseConcurrent<T, E, D, F, Fut, DefFut> {
+        init: Option<(Result<T, E>, D, F)>,
+        #[pin]
+        op: Option<Fut>,
+        #[pin]
+        default: Option<DefFut>,
+    }
+}
+
+impl<T, E, D, F, Fut, DefFut> AsyncMapOrElseConcurrent<T, E, D, F, Fut, DefFut> {
+    pub(crate) fn new(this: Result<T, E>, default: D, op: F) -> Self {
+        Self {
+            init: Some((this, default, op)),
+            op: None,
+            default: None,
+        }
+    }
+}
+
+impl<T, E, U, D, F, Fut, DefFut> Future for AsyncMapOrElseConcurrent<T, E, D, F, Fut, DefFut>
+where
+    D: FnOnce() -> DefFut,
+    F: FnOnce(T) -> Fut,
+    DefFut: Future<Output = U>,
+    Fut: Future<Output = U>,
+{
+    type Output = U;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let mut this = self.project();
+
+        if let Some((result, default, op)) = this.init.take() {
+            this.default.set(Some(default()));
+            if let Ok(value) = result {
+                this.op.set(Some(op(value)));
+            }
+        }
+
+        if let Some(op) = this.op.as_mut().as_pin_mut() {
+            if let Some(default) = this.default.as_mut().as_pin_mut() {
+                if default.poll(cx).is_ready() {
+                    this.default.set(None);
+                }
+            }
+            let output = ready!(op.poll(cx));
+            this.op.set(None);
+            this.default.set(None);
+            return Poll::Ready(output);
+        }
+
+        match this.default.as_mut().as_pin_mut() {
+            Some(default) => {
+                let output = ready!(default.poll(cx));
+                this.default.set(None);
+                Poll::Ready(output)
+            }
+            None => polled_after_completion("async_map_or_else_concurrent"),
+        }
+    }
+}
+
+pin_project! {
+    /// Future for the [`async_try_map_both`](crate::AsyncResultExt::async_try_map_both) method.
+    #[must_use = "futures do nothing unless you `.await` or poll them"]
+    pub struct AsyncTryMapBoth<T, E, F, G, FFut, GFut> {
+        #[pin]
+        state: State<(Result<T, E>, F, G), Either<FFut, GFut>, ()>,
+    }
+}
+
+impl<T, E, F, G, FFut, GFut> AsyncTryMapBoth<T, E, F, G, FFut, GFut> {
+    pub(crate) fn new(this: Result<T, E>, ok_op: F, err_op: G) -> Self {
+        Self {
+            state: State::new((this, ok_op, err_op)),
+        }
+    }
+}
+
+impl<T, E, U, O, M, F, G, FFut, GFut> Future for AsyncTryMapBoth<T, E, F, G, FFut, GFut>
+where
+    O: From<M>,
+    F: FnOnce(T) -> FFut,
+    G: FnOnce(E) -> GFut,
+    FFut: Future<Output = Result<U, M>>,
+    GFut: Future<Output = Result<O, M>>,
+{
+    type Output = Result<U, O>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        self.project().state.poll_with(
+            "async_try_map_both",
+            cx,
+            |(this, ok_op, err_op)| match this {
+                Ok(value) => Step::Run(Either::Left { fut: ok_op(value) }, ()),
+                Err(err) => Step::Run(Either::Right { fut: err_op(err) }, ()),
+            },
+            |output, ()| match output {
+                EitherOutput::Left(output) => output.map_err(O::from),
+                EitherOutput::Right(Ok(mapped)) => Err(mapped),
+                EitherOutput::Right(Err(failure)) => Err(O::from(failure)),
+            },
+        )
+    }
+}
+
+pin_project! {
+    /// Future for the [`async_inspect_try`](crate::AsyncResultExt::async_inspect_try) method.
+    #[must_use = "futures do nothing unless you `.await` or poll them"]
+    pub struct AsyncInspectTry<T, E, F, Fut> {
+        #[pin]
+        state: State<(Result<T, E>, F), Fut, T>,
+    }
+}
+
+impl<T, E, F, Fut> AsyncInspectTry<T, E, F, Fut> {
+    pub(crate) fn new(this: Result<T, E>, op: F) -> Self {
+        Self {
+            state: State::new((this, op)),
+        }
+    }
+}
+
+impl<T, E, F, Fut> Future for AsyncInspectTry<T, E, F, Fut>
+where
+    F: FnOnce(&T) -> Fut,
+    Fut: Future<Output = Result<(), E>>,
+{
+    type Output = Result<T, E>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        self.project().state.poll_with(
+            "async_inspect_try",
+            cx,
+            |(this, op)| match this {
+                Ok(value) => Step::Run(op(&value), value),
+                Err(err) => Step::Ready(Err(err)),
+            },
+            |output, value| output.map(|()| value),
+        )
+    }
+}
+
+pin_project! {
+    /// Future for the [`async_inspect`](crate::AsyncResultRefExt::async_inspect) method on a
+    /// borrowed result.
+    #[must_use = "futures do nothing unless you `.await` or poll them"]
+    pub struct AsyncInspectRef<'a, T, E, F, Fut> {
+        #[pin]
+        state: State<(&'a Result<T, E>, F), Fut, &'a Result<T, E>>,
+    }
+}
+
+impl<'a, T, E, F, Fut> AsyncInspectRef<'a, T, E, F, Fut> {
+    pub(crate) fn new(this: &'a Result<T, E>, op: F) -> Self {
+        Self {
+            state: State::new((this, op)),
+        }
+    }
+}
+
+impl<'a, T, E, F, Fut> Future for AsyncInspectRef<'a, T, E, F, Fut>
+where
+    F: FnOnce(&'a T) -> Fut,
+    Fut: Future<Output = ()>,
+{
+    type Output = &'a Result<T, E>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        self.project().state.poll_with(
+            "async_inspect",
+            cx,
+            |(this, op)| match this {
+                Ok(value) => Step::Run(op(value), this),
+                Err(_) => Step::Ready(this),
+            },
+            |(), this| this,
+        )
+    }
+}
+
+pin_project! {
+    /// Future for the [`async_inspect_err`](crate::AsyncResultRefExt::async_inspect_err) method
+    /// on a borrowed result.
+    #[must_use = "futures do nothing unless you `.await` or poll them"]
+    pub struct AsyncInspectErrRef<'a, T, E, F, Fut> {
+        #[pin]
+        state: State<(&'a Result<T, E>, F), Fut, &'a Result<T, E>>,
+    }
+}
+
+impl<'a, T, E, F, Fut> AsyncInspectErrRef<'a, T, E, F, Fut> {
+    pub(crate) fn new(this: &'a Result<T, E>, op: F) -> Self {
+        Self {
+            state: State::new((this, op)),
+        }
+    }
+}
+
+impl<'a, T, E, F, Fut> Future for AsyncInspectErrRef<'a, T, E, F, Fut>
+where
+    F: FnOnce(&'a E) -> Fut,
+    Fut: Future<Output = ()>,
+{
+    type Output = &'a Result<T, E>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        self.project().state.poll_with(
+            "async_inspect_err",
+            cx,
+            |(this, op)| match this {
+                Ok(_) => Step::Ready(this),
+                Err(err) => Step::Run(op(err), this),
+            },
+            |(), this| this,
+        )
+    }
+}
+
+pin_project! {
+    /// Future for the [`async_tap`](crate::AsyncResultExt::async_tap) method.
+    #[must_use = "futures do nothing unless you `.await` or poll them"]
+    pub struct AsyncTap<T, E, F, Fut> {
+        #[pin]
+        state: State<(Result<T, E>, F), Fut, Result<T, E>>,
+    }
+}
+
+impl<T, E, F, Fut> AsyncTap<T, E, F, Fut> {
+    pub(crate) fn new(this: Result<T, E>, op: F) -> Self {
+        Self {
+            state: State::new((this, op)),
+        }
+    }
+}
+
+impl<T, E, F, Fut> Future for AsyncTap<T, E, F, Fut>
+where
+    F: FnOnce(&Result<T, E>) -> Fut,
+    Fut: Future<Output = ()>,
+{
+    type Output = Result<T, E>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        self.project().state.poll_with(
+            "async_tap",
+            cx,
+            |(this, op)| Step::Run(op(&this), this),
+            |(), this| this,
+        )
+    }
+}
+
+pin_project! {
+    /// Future for the [`async_filter_map`](crate::AsyncResultExt::async_filter_map) method.
+    #[must_use = "futures do nothing unless you `.await` or poll them"]
+    pub struct AsyncFilterMap<T, E, F, Fut> {
+        #[pin]
+        state: State<(Result<T, E>, F), Fut, ()>,
+    }
+}
+
+impl<T, E, F, Fut> AsyncFilterMap<T, E, F, Fut> {
+    pub(crate) fn new(this: Result<T, E>, op: F) -> Self {
+        Self {
+            state: State::new((this, op)),
+        }
+    }
+}
+
+impl<T, E, U, F, Fut> Future for AsyncFilterMap<T, E, F, Fut>
+where
+    F: FnOnce(T) -> Fut,
+    Fut: Future<Output = Result<Option<U>, E>>,
+{
+    type Output = Result<Option<U>, E>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        self.project().state.poll_with(
+            "async_filter_map",
+            cx,
+            |(this, op)| match this {
+                Ok(value) => Step::Run(op(value), ()),
+                Err(err) => Step::Ready(Err(err)),
+            },
+            |output, ()| output,
+        )
+    }
+}
+
+pin_project! {
+    /// Future for the [`async_map_or_default`](crate::AsyncResultExt::async_map_or_default) method.
+    #[must_use = "futures do nothing unless you `.await` or poll them"]
+    pub struct AsyncMapOrDefault<T, E, F, Fut> {
+        #[pin]
+        state: State<(Result<T, E>, F), Fut, ()>,
+    }
+}
+
+impl<T, E, F, Fut> AsyncMapOrDefault<T, E, F, Fut> {
+    pub(crate) fn new(this: Result<T, E>, op: F) -> Self {
+        Self {
+            state: State::new((this, op)),
+        }
+    }
+}
+
+impl<T, E, U, F, Fut> Future for AsyncMapOrDefault<T, E, F, Fut>
+where
+    U: Default,
+    F: FnOnce(T) -> Fut,
+    Fut: Future<Output = U>,
+{
+    type Output = U;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        self.project().state.poll_with(
+            "async_map_or_default",
+            cx,
+            |(this, op)| match this {
+                Ok(value) => Step::Run(op(value), ()),
+                Err(_) => Step::Ready(U::default()),
+            },
+            |output, ()| output,
+        )
+    }
+}
+
+pin_project! {
+    /// Future for the [`async_expect`](crate::AsyncResultExt::async_expect) method.
+    #[must_use = "futures do nothing unless you `.await` or poll them"]
+    pub struct AsyncExpect<T, E, F, Fut> {
+        #[pin]
+        state: State<(Result<T, E>, F), Fut, ()>,
+    }
+}
+
+impl<T, E, F, Fut> AsyncExpect<T, E, F, Fut> {
+    pub(crate) fn new(this: Result<T, E>, msg: F) -> Self {
+        Self {
+            state: State::new((this, msg)),
+        }
+    }
+}
+
+impl<T, E, M, F, Fut> Future for AsyncExpect<T, E, F, Fut>
+where
+    M: fmt::Display,
+    F: FnOnce(&E) -> Fut,
+    Fut: Future<Output = M>,
+{
+    type Output = T;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        self.project().state.poll_with(
+            "async_expect",
+            cx,
+            |(this, msg)| match this {
+                Ok(value) => Step::Ready(value),
+                Err(err) => Step::Run(msg(&err), ()),
+            },
+            |msg, ()| panic!("{msg}"),
+        )
+    }
+}
+
+pin_project! {
+    /// Future for the [`async_contains`](crate::AsyncResultExt::async_contains) method.
+    #[must_use = "futures do nothing unless you `.await` or poll them"]
+    pub struct AsyncContains<T, E, F, Fut> {
+        #[pin]
+        state: State<(Result<T, E>, F), Fut, T>,
+    }
+}
+
+impl<T, E, F, Fut> AsyncContains<T, E, F, Fut> {
+    pub(crate) fn new(this: Result<T, E>, op: F) -> Self {
+        Self {
+            state: State::new((this, op)),
+        }
+    }
+}
+
+impl<T, E, F, Fut> Future for AsyncContains<T, E, F, Fut>
+where
+    T: PartialEq,
+    F: FnOnce() -> Fut,
+    Fut: Future<Output = T>,
+{
+    type Output = bool;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        self.project().state.poll_with(
+            "async_contains",
+            cx,
+            |(this, op)| match this {
+                Ok(value) => Step::Run(op(), value),
+                Err(_) => Step::Ready(false),
+            },
+            |expected, value| value == expected,
+        )
+    }
+}
+
+pin_project! {
+    /// Future for the [`async_contains_err`](crate::AsyncResultExt::async_contains_err) method.
+    #[must_use = "futures do nothing unless you `.await` or poll them"]
+    pub struct AsyncContainsErr<T, E, F, Fut> {
+        #[pin]
+        state: State<(Result<T, E>, F), Fut, E>,
+    }
+}
+
+impl<T, E, F, Fut> AsyncContainsErr<T, E, F, Fut> {
+    pub(crate) fn new(this: Result<T, E>, op: F) -> Self {
+        Self {
+            state: State::new((this, op)),
+        }
+    }
+}
+
+impl<T, E, F, Fut> Future for AsyncContainsErr<T, E, F, Fut>
+where
+    E: PartialEq,
+    F: FnOnce() -> Fut,
+    Fut: Future<Output = E>,
+{
+    type Output = bool;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        self.project().state.poll_with(
+            "async_contains_err",
+            cx,
+            |(this, op)| match this {
+                Ok(_) => Step::Ready(false),
+                Err(err) => Step::Run(op(), err),
+            },
+            |expected, err| err == expected,
+        )
+    }
+}
+
+pin_project! {
+    /// Future for the [`async_and_then_ctx`](crate::AsyncResultExt::async_and_then_ctx) method.
+    #[must_use = "futures do nothing unless you `.await` or poll them"]
+    pub struct AsyncAndThenCtx<'c, C: ?Sized, T, E, F, Fut> {
+        #[pin]
+        state: State<(Result<T, E>, &'c C, F), Fut, ()>,
+    }
+}
+
+impl<'c, C: ?Sized, T, E, F, Fut> AsyncAndThenCtx<'c, C, T, E, F, Fut> {
+    pub(crate) fn new(this: Result<T, E>, ctx: &'c C, op: F) -> Self {
+        Self {
+            state: State::new((this, ctx, op)),
+        }
+    }
+}
+
+impl<'c, C: ?Sized, T, E, U, F, Fut> Future for AsyncAndThenCtx<'c, C, T, E, F, Fut>
+where
+    F: FnOnce(&'c C, T) -> Fut,
+    Fut: Future<Output = Result<U, E>>,
+{
+    type Output = Result<U, E>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        self.project().state.poll_with(
+            "async_and_then_ctx",
+            cx,
+            |(this, ctx, op)| match this {
+                Ok(value) => Step::Run(op(ctx, value), ()),
+                Err(err) => Step::Ready(Err(err)),
+            },
+            |output, ()| output,
+        )
+    }
+}
+
+#[cfg(feature = "futures")]
+pin_project! {
+    /// Future for the [`async_split`](crate::AsyncResultExt::async_split) method.
+    #[must_use = "futures do nothing unless you `.await` or poll them"]
+    pub struct AsyncSplit<T, E, F, G, FFut, GFut> {
+        #[pin]
+        state: State<(Result<T, E>, F, G), Either<FFut, GFut>, ()>,
+    }
+}
+
+#[cfg(feature = "futures")]
+impl<T, E, F, G, FFut, GFut> AsyncSplit<T, E, F, G, FFut, GFut> {
+    pub(crate) fn new(this: Result<T, E>, ok_op: F, err_op: G) -> Self {
+        Self {
+            state: State::new((this, ok_op, err_op)),
+        }
+    }
+}
+
+#[cfg(feature = "futures")]
+impl<T, E, F, G, FFut, GFut> Future for AsyncSplit<T, E, F, G, FFut, GFut>
+where
+    F: FnOnce(T) -> FFut,
+    G: FnOnce(E) -> GFut,
+    FFut: Future,
+    GFut: Future,
+{
+    type Output = futures_util::future::Either<FFut::Output, GFut::Output>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        self.project().state.poll_with(
+            "async_split",
+            cx,
+            |(this, ok_op, err_op)| match this {
+                Ok(value) => Step::Run(Either::Left { fut: ok_op(value) }, ()),
+                Err(err) => Step::Run(Either::Right { fut: err_op(err) }, ()),
+            },
+            |output, ()| match output {
+                EitherOutput::Left(left) => futures_util::future::Either::Left(left),
+                EitherOutput::Right(right) => futures_util::future::Either::Right(right),
+            },
+        )
+    }
+}
+
+pin_project! {
+    /// Future for the [`async_map_timed`](crate::AsyncResultExt::async_map_timed) method.
+    #[must_use = "futures do nothing unless you `.await` or poll them"]
+    pub struct AsyncMapTimed<T, E, C, I, F, Fut> {
+        #[pin]
+        state: State<(Result<T, E>, C, F), Fut, (C, I)>,
+    }
+}
+
+impl<T, E, C, I, F, Fut> AsyncMapTimed<T, E, C, I, F, Fut> {
+    pub(crate) fn new(this: Result<T, E>, clock: C, op: F) -> Self {
+        Self {
+            state: State::new((this, clock, op)),
+        }
+    }
+}
+
+impl<T, E, U, C, I, F, Fut> Future for AsyncMapTimed<T, E, C, I, F, Fut>
+where
+    C: Fn() -> I,
+    I: Sub<Output = Duration>,
+    F: FnOnce(T) -> Fut,
+    Fut: Future<Output = U>,
+{
+    type Output = (Result<U, E>, Option<Duration>);
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        self.project().state.poll_with(
+            "async_map_timed",
+            cx,
+            |(this, clock, op)| match this {
+                Ok(value) => {
+                    let start = clock();
+                    Step::Run(op(value), (clock, start))
+                }
+                Err(err) => Step::Ready((Err(err), None)),
+            },
+            |output, (clock, start)| (Ok(output), Some(clock() - start)),
+        )
+    }
+}
+
+pin_project! {
+    /// Future for the [`async_or_default_with`](crate::AsyncResultExt::async_or_default_with) method.
+    #[must_use = "futures do nothing unless you `.await` or poll them"]
+    pub struct AsyncOrDefaultWith<T, E, F, Fut> {
+        #[pin]
+        state: State<(Result<T, E>, F), Fut, E>,
+    }
+}
+
+impl<T, E, F, Fut> AsyncOrDefaultWith<T, E, F, Fut> {
+    pub(crate) fn new(this: Result<T, E>, op: F) -> Self {
+        Self {
+            state: State::new((this, op)),
+        }
+    }
+}
+
+impl<T, E, F, Fut> Future for AsyncOrDefaultWith<T, E, F, Fut>
+where
+    F: FnOnce(&E) -> Fut,
+    Fut: Future<Output = Option<T>>,
+{
+    type Output = Result<T, E>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        self.project().state.poll_with(
+            "async_or_default_with",
+            cx,
+            |(this, op)| match this {
+                Ok(value) => Step::Ready(Ok(value)),
+                Err(err) => Step::Run(op(&err), err),
+            },
+            |default, err| default.ok_or(err),
+        )
+    }
+}
+
+pin_project! {
+    /// Future for the [`async_replace_err`](crate::AsyncResultExt::async_replace_err) method.
+    #[must_use = "futures do nothing unless you `.await` or poll them"]
+    pub struct AsyncReplaceErr<T, E, F, Fut> {
+        #[pin]
+        state: State<(Result<T, E>, F), Fut, ()>,
+    }
+}
+
+impl<T, E, F, Fut> AsyncReplaceErr<T, E, F, Fut> {
+    pub(crate) fn new(this: Result<T, E>, op: F) -> Self {
+        Self {
+            state: State::new((this, op)),
+        }
+    }
+}
+
+impl<T, E, O, F, Fut> Future for AsyncReplaceErr<T, E, F, Fut>
+where
+    F: FnOnce() -> Fut,
+    Fut: Future<Output = O>,
+{
+    type Output = Result<T, O>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        self.project().state.poll_with(
+            "async_replace_err",
+            cx,
+            |(this, op)| match this {
+                Ok(value) => Step::Ready(Ok(value)),
+                Err(err) => {
+                    drop(err);
+                    Step::Run(op(), ())
+                }
+            },
+            |err, ()| Err(err),
+        )
+    }
+}
+
+pin_project! {
+    /// Future for the [`async_then_unit`](crate::AsyncResultExt::async_then_unit) method.
+    #[must_use = "futures do nothing unless you `.await` or poll them"]
+    pub struct AsyncThenUnit<T, E, F, Fut> {
+        #[pin]
+        state: State<(Result<T, E>, F), Fut, ()>,
+    }
+}
+
+impl<T, E, F, Fut> AsyncThenUnit<T, E, F, Fut> {
+    pub(crate) fn new(this: Result<T, E>, op: F) -> Self {
+        Self {
+            state: State::new((this, op)),
+        }
+    }
+}
+
+impl<T, E, F, Fut> Future for AsyncThenUnit<T, E, F, Fut>
+where
+    F: FnOnce(T) -> Fut,
+    Fut: Future<Output = ()>,
+{
+    type Output = Result<(), E>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        self.project().state.poll_with(
+            "async_then_unit",
+            cx,
+            |(this, op)| match this {
+                Ok(value) => Step::Run(op(value), ()),
+                Err(err) => Step::Ready(Err(err)),
+            },
+            |(), ()| Ok(()),
+        )
+    }
+}
+
+pin_project! {
+    /// Future for the [`async_and_then_ref`](crate::AsyncResultExt::async_and_then_ref) method.
+    #[must_use = "futures do nothing unless you `.await` or poll them"]
+    pub struct AsyncAndThenRef<T, E, F, Fut> {
+        #[pin]
+        state: State<(Result<T, E>, F), Fut, T>,
+    }
+}
+
+impl<T, E, F, Fut> AsyncAndThenRef<T, E, F, Fut> {
+    pub(crate) fn new(this: Result<T, E>, op: F) -> Self {
+        Self {
+            state: State::new((this, op)),
+        }
+    }
+}
+
+impl<T, E, U, F, Fut> Future for AsyncAndThenRef<T, E, F, Fut>
+where
+    F: FnOnce(&T) -> Fut,
+    Fut: Future<Output = Result<U, E>>,
+{
+    type Output = Result<(T, U), E>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        self.project().state.poll_with(
+            "async_and_then_ref",
+            cx,
+            |(this, op)| match this {
+                Ok(value) => Step::Run(op(&value), value),
+                Err(err) => Step::Ready(Err(err)),
+            },
+            |output, value| output.map(|computed| (value, computed)),
+        )
+    }
+}
+
+pin_project! {
+    /// Future for the [`async_and_then_timeout`](crate::AsyncResultExt::async_and_then_timeout) method.
+    #[must_use = "futures do nothing unless you `.await` or poll them"]
+    pub struct AsyncAndThenTimeout<T, E, S, D, F, Fut, SleepFut> {
+        #[pin]
+        state: State<(Result<T, E>, Duration, S, D, F), Timeout<Fut, SleepFut>, D>,
+    }
+}
+
+impl<T, E, S, D, F, Fut, SleepFut> AsyncAndThenTimeout<T, E, S, D, F, Fut, SleepFut> {
+    pub(crate) fn new(this: Result<T, E>, dur: Duration, sleep: S, on_timeout: D, op: F) -> Self {
+        Self {
+            state: State::new((this, dur, sleep, on_timeout, op)),
+        }
+    }
+}
+
+impl<T, E, U, S, D, F, Fut, SleepFut> Future for AsyncAndThenTimeout<T, E, S, D, F, Fut, SleepFut>
+where
+    S: FnOnce(Duration) -> SleepFut,
+    SleepFut: Future<Output = ()>,
+    D: FnOnce() -> E,
+    F: FnOnce(T) -> Fut,
+    Fut: Future<Output = Result<U, E>>,
+{
+    type Output = Result<U, E>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        self.project().state.poll_with(
+            "async_and_then_timeout",
+            cx,
+            |(this, dur, sleep, on_timeout, op)| match this {
+                Ok(value) => Step::Run(timeout(op(value), sleep(dur)), on_timeout),
+                Err(err) => Step::Ready(Err(err)),
+            },
+            |output, on_timeout| output.map_err(|err| err.into_error(on_timeout)),
+        )
+    }
+}
+
+pin_project! {
+    /// Future for the [`async_or_else_into`](crate::AsyncResultExt::async_or_else_into) method.
+    #[must_use = "futures do nothing unless you `.await` or poll them"]
+    pub struct AsyncOrElseInto<T, E, O, F, Fut> {
+        #[pin]
+        state: State<(Result<T, E>, F), Fut, PhantomData<fn() -> O>>,
+    }
+}
+
+impl<T, E, O, F, Fut> AsyncOrElseInto<T, E, O, F, Fut> {
+    pub(crate) fn new(this: Result<T, E>, op: F) -> Self {
+        Self {
+            state: State::new((this, op)),
+        }
+    }
+}
+
+impl<T, E, O, M, F, Fut> Future for AsyncOrElseInto<T, E, O, F, Fut>
+where
+    O: From<M>,
+    F: FnOnce(E) -> Fut,
+    Fut: Future<Output = Result<T, M>>,
+{
+    type Output = Result<T, O>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        self.project().state.poll_with(
+            "async_or_else_into",
+            cx,
+            |(this, op)| match this {
+                Ok(value) => Step::Ready(Ok(value)),
+                Err(err) => Step::Run(op(err), PhantomData),
+            },
+            |output, PhantomData| output.map_err(O::from),
+        )
+    }
+}
+
+#[cfg(all(feature = "futures", feature = "std"))]
+pin_project! {
+    /// Future for the [`async_and_then_catch`](crate::AsyncResultExt::async_and_then_catch) method.
+    #[must_use = "futures do nothing unless you `.await` or poll them"]
+    pub struct AsyncAndThenCatch<T, E, F, Fut, D> {
+        #[pin]
+        state: State<(Result<T, E>, F, D), futures_util::future::CatchUnwind<Fut>, D>,
+    }
+}
+
+#[cfg(all(feature = "futures", feature = "std"))]
+impl<T, E, F, Fut, D> AsyncAndThenCatch<T, E, F, Fut, D> {
+    pub(crate) fn new(this: Result<T, E>, op: F, on_panic: D) -> Self {
+        Self {
+            state: State::new((this, op, on_panic)),
+        }
+    }
+}
+
+#[cfg(all(feature = "futures", feature = "std"))]
+impl<T, E, U, F, Fut, D> Future for AsyncAndThenCatch<T, E, F, Fut, D>
+where
+    F: FnOnce(T) -> Fut,
+    Fut: Future<Output = Result<U, E>> + std::panic::UnwindSafe,
+    D: FnOnce() -> E,
+{
+    type Output = Result<U, E>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        use futures_util::FutureExt;
+
+        self.project().state.poll_with(
+            "async_and_then_catch",
+            cx,
+            |(this, op, on_panic)| match this {
+                Ok(value) => Step::Run(op(value).catch_unwind(), on_panic),
+                Err(err) => Step::Ready(Err(err)),
+            },
+            |output, on_panic| output.unwrap_or_else(|_payload| Err(on_panic())),
+        )
+    }
+}
+
+pin_project! {
+    /// Future for the [`async_map_err_inspect`](crate::AsyncResultExt::async_map_err_inspect) method.
+    #[must_use = "futures do nothing unless you `.await` or poll them"]
+    pub struct AsyncMapErrInspect<T, E, F, G, FFut, GFut> {
+        #[pin]
+        state: State<(Result<T, E>, F, G), Either<FFut, GFut>, Option<T>>,
+    }
+}
+
+impl<T, E, F, G, FFut, GFut> AsyncMapErrInspect<T, E, F, G, FFut, GFut> {
+    pub(crate) fn new(this: Result<T, E>, err_op: F, ok_inspect: G) -> Self {
+        Self {
+            state: State::new((this, err_op, ok_inspect)),
+        }
+    }
+}
+
+impl<T, E, O, F, G, FFut, GFut> Future for AsyncMapErrInspect<T, E, F, G, FFut, GFut>
+where
+    F: FnOnce(E) -> FFut,
+    G: FnOnce(&T) -> GFut,
+    FFut: Future<Output = O>,
+    GFut: Future<Output = ()>,
+{
+    type Output = Result<T, O>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        self.project().state.poll_with(
+            "async_map_err_inspect",
+            cx,
+            |(this, err_op, ok_inspect)| match this {
+                Ok(value) => Step::Run(
+                    Either::Right {
+                        fut: ok_inspect(&value),
+                    },
+                    Some(value),
+                ),
+                Err(err) => Step::Run(Either::Left { fut: err_op(err) }, None),
+            },
+            |output, value| match (output, value) {
+                (EitherOutput::Left(mapped), _) => Err(mapped),
+                (EitherOutput::Right(()), Some(value)) => Ok(value),
+                (EitherOutput::Right(()), None) => unreachable!(),
+            },
+        )
+    }
+}
+
+pin_project! {
+    /// Future for the [`async_flatten_err`](crate::AsyncFlattenErrExt::async_flatten_err) method.
+    #[must_use = "futures do nothing unless you `.await` or poll them"]
+    pub struct AsyncFlattenErr<T, E1, E2, F, Fut> {
+        #[pin]
+        state: State<(Result<T, Result<E1, E2>>, F), Fut, ()>,
+    }
+}
+
+impl<T, E1, E2, F, Fut> AsyncFlattenErr<T, E1, E2, F, Fut> {
+    pub(crate) fn new(this: Result<T, Result<E1, E2>>, op: F) -> Self {
+        Self {
+            state: State::new((this, op)),
+        }
+    }
+}
+
+impl<T, E1, E2, F, Fut> Future for AsyncFlattenErr<T, E1, E2, F, Fut>
+where
+    F: FnOnce(E2) -> Fut,
+    Fut: Future<Output = E1>,
+{
+    type Output = Result<T, E1>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        self.project().state.poll_with(
+            "async_flatten_err",
+            cx,
+            |(this, op)| match this {
+                Ok(value) => Step::Ready(Ok(value)),
+                Err(Ok(err)) => Step::Ready(Err(err)),
+                Err(Err(err)) => Step::Run(op(err), ()),
+            },
+            |err, ()| Err(err),
+        )
+    }
+}
+
+pin_project! {
+    /// Future for the [`async_and_then_retry`](crate::AsyncResultExt::async_and_then_retry) method.
+    #[must_use = "futures do nothing unless you `.await` or poll them"]
+    pub struct AsyncAndThenRetry<T, E, F, Fut> {
+        this: Option<Result<T, E>>,
+        op: F,
+        remaining: usize,
+        #[pin]
+        pending: Option<Fut>,
+    }
+}
+
+impl<T, E, F, Fut> AsyncAndThenRetry<T, E, F, Fut> {
+    pub(crate) fn new(this: Result<T, E>, attempts: usize, op: F) -> Self {
+        Self {
+            this: Some(this),
+            op,
+            remaining: attempts.max(1),
+            pending: None,
+        }
+    }
+}
+
+impl<T, E, U, F, Fut> Future for AsyncAndThenRetry<T, E, F, Fut>
+where
+    T: Clone,
+    F: FnMut(T) -> Fut,
+    Fut: Future<Output = Result<U, E>>,
+{
+    type Output = Result<U, E>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let mut this = self.project();
+        loop {
+            if let Some(fut) = this.pending.as_mut().as_pin_mut() {
+                let output = ready!(fut.poll(cx));
+                this.pending.set(None);
+                *this.remaining -= 1;
+                if output.is_ok() || *this.remaining == 0 {
+                    *this.this = None;
+                    return Poll::Ready(output);
+                }
+            }
+
+            match this.this.take() {
+                Some(Ok(value)) => {
+                    // The last attempt takes the value instead of a clone.
+                    let arg = if *this.remaining > 1 {
+                        let arg = value.clone();
+                        *this.this = Some(Ok(value));
+                        arg
+                    } else {
+                        value
+                    };
+                    this.pending.set(Some((this.op)(arg)));
+                }
+                Some(Err(err)) => return Poll::Ready(Err(err)),
+                None => polled_after_completion("async_and_then_retry"),
+            }
+        }
+    }
+}
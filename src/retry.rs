@@ -0,0 +1,451 @@
+//! Retrying fallible async operations.
+//!
+//! These helpers re-invoke a factory closure that produces a fresh future per
+//! attempt, so they work with any executor. Waiting between attempts goes through
+//! a caller-supplied sleep function, so no timer or runtime is assumed.
+
+use core::future::Future;
+use core::time::Duration;
+
+/// Calls `op` until it returns `Ok`, at most `attempts` times.
+///
+/// Returns the first `Ok`, or the `Err` of the last attempt once all attempts
+/// fail. `op` is always called at least once, so `attempts == 0` behaves like `1`.
+///
+/// # Cancellation
+///
+/// Dropping the future drops the attempt in flight; no further attempts are made.
+///
+/// ```
+/// use async_result_ext::retry::retry;
+///
+/// # futures::executor::block_on(async {
+/// let mut calls = 0;
+/// let res = retry(3, || {
+///     calls += 1;
+///     let attempt = calls;
+///     async move { if attempt < 3 { Err("transient") } else { Ok(attempt) } }
+/// })
+/// .await;
+/// assert_eq!(res, Ok(3));
+/// # });
+/// ```
+pub async fn retry<T, E, F, Fut>(attempts: usize, op: F) -> Result<T, E>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T, E>>,
+{
+    retry_if(attempts, |_| true, op).await
+}
+
+/// Like [`retry`], but only retries errors for which `should_retry` returns `true`.
+///
+/// Any other error is returned immediately, without using up the remaining attempts.
+///
+/// ```
+/// use async_result_ext::retry::retry_if;
+///
+/// # futures::executor::block_on(async {
+/// let mut calls = 0;
+/// let res: Result<(), u16> = retry_if(
+///     5,
+///     |status| *status == 503,
+///     || {
+///         calls += 1;
+///         async { Err(400) }
+///     },
+/// )
+/// .await;
+/// assert_eq!(res, Err(400));
+/// assert_eq!(calls, 1);
+/// # });
+/// ```
+pub async fn retry_if<T, E, P, F, Fut>(
+    attempts: usize,
+    mut should_retry: P,
+    mut op: F,
+) -> Result<T, E>
+where
+    P: FnMut(&E) -> bool,
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T, E>>,
+{
+    let mut remaining = attempts.max(1);
+    loop {
+        match op().await {
+            Ok(value) => return Ok(value),
+            Err(err) => {
+                remaining -= 1;
+                if remaining == 0 || !should_retry(&err) {
+                    return Err(err);
+                }
+            }
+        }
+    }
+}
+
+/// Delay schedule for [`retry_with_backoff`].
+///
+/// The first retry waits `initial`; each later one waits `multiplier` times
+/// longer than the previous, capped at `max`. An optional `jitter` function is
+/// applied to every capped delay, e.g. to randomize it with the caller's RNG.
+///
+/// ```
+/// use core::time::Duration;
+/// use async_result_ext::retry::Backoff;
+///
+/// let policy = Backoff::new()
+///     .attempts(5)
+///     .initial(Duration::from_millis(50))
+///     .max(Duration::from_secs(1))
+///     .multiplier(3.0);
+/// # let _ = policy;
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct Backoff {
+    attempts: usize,
+    initial: Duration,
+    max: Duration,
+    multiplier: f64,
+    jitter: Option<fn(Duration) -> Duration>,
+}
+
+impl Backoff {
+    /// Three attempts, starting at 100ms, doubling up to 10s, without jitter.
+    pub const fn new() -> Self {
+        Self {
+            attempts: 3,
+            initial: Duration::from_millis(100),
+            max: Duration::from_secs(10),
+            multiplier: 2.0,
+            jitter: None,
+        }
+    }
+
+    /// Sets the total number of attempts, including the first one.
+    ///
+    /// `0` behaves like `1`, as in [`retry`].
+    pub const fn attempts(mut self, attempts: usize) -> Self {
+        self.attempts = attempts;
+        self
+    }
+
+    /// Sets the delay before the first retry.
+    pub const fn initial(mut self, initial: Duration) -> Self {
+        self.initial = initial;
+        self
+    }
+
+    /// Sets the upper bound for any single delay, before jitter.
+    pub const fn max(mut self, max: Duration) -> Self {
+        self.max = max;
+        self
+    }
+
+    /// Sets the factor each delay grows by.
+    pub const fn multiplier(mut self, multiplier: f64) -> Self {
+        self.multiplier = multiplier;
+        self
+    }
+
+    /// Sets a function applied to every delay after capping.
+    pub const fn jitter(mut self, jitter: fn(Duration) -> Duration) -> Self {
+        self.jitter = Some(jitter);
+        self
+    }
+
+    fn grow(&self, delay: Duration) -> Duration {
+        Duration::try_from_secs_f64(delay.as_secs_f64() * self.multiplier)
+            .map_or(self.max, |next| next.min(self.max))
+    }
+}
+
+impl Default for Backoff {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Like [`retry`], but awaits `sleep` between attempts as scheduled by `policy`.
+///
+/// `sleep` receives each delay and returns a future that completes once it has
+/// elapsed, e.g. `tokio::time::sleep` or a timer from any other runtime.
+///
+/// # Cancellation
+///
+/// Dropping the future drops the attempt or sleep in flight; no further
+/// attempts are made.
+///
+/// ```
+/// use core::time::Duration;
+/// use async_result_ext::retry::{Backoff, retry_with_backoff};
+///
+/// # futures::executor::block_on(async {
+/// let mut delays = Vec::new();
+/// let res: Result<(), &str> = retry_with_backoff(
+///     Backoff::new().attempts(3),
+///     |delay| {
+///         delays.push(delay);
+///         async {}
+///     },
+///     || async { Err("unavailable") },
+/// )
+/// .await;
+/// assert_eq!(res, Err("unavailable"));
+/// assert_eq!(delays, [Duration::from_millis(100), Duration::from_millis(200)]);
+/// # });
+/// ```
+pub async fn retry_with_backoff<T, E, F, Fut, S, SleepFut>(
+    policy: Backoff,
+    mut sleep: S,
+    mut op: F,
+) -> Result<T, E>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T, E>>,
+    S: FnMut(Duration) -> SleepFut,
+    SleepFut: Future<Output = ()>,
+{
+    let mut remaining = policy.attempts.max(1);
+    let mut delay = policy.initial.min(policy.max);
+    loop {
+        match op().await {
+            Ok(value) => return Ok(value),
+            Err(err) => {
+                remaining -= 1;
+                if remaining == 0 {
+                    return Err(err);
+                }
+            }
+        }
+        sleep(policy.jitter.map_or(delay, |jitter| jitter(delay))).await;
+        delay = policy.grow(delay);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::{poll_once, yield_now};
+    use core::pin::pin;
+    use futures::executor::block_on;
+    use std::vec::Vec;
+
+    #[test]
+    fn test_retry_first_try() {
+        block_on(async {
+            let mut calls = 0;
+            let res: Result<i32, &str> = retry(3, || {
+                calls += 1;
+                async { Ok(7) }
+            })
+            .await;
+            assert_eq!(res, Ok(7));
+            assert_eq!(calls, 1);
+        });
+    }
+
+    #[test]
+    fn test_retry_after_failures() {
+        block_on(async {
+            let mut calls = 0;
+            let res = retry(5, || {
+                calls += 1;
+                let attempt = calls;
+                async move {
+                    if attempt < 3 {
+                        Err(attempt)
+                    } else {
+                        Ok(attempt)
+                    }
+                }
+            })
+            .await;
+            assert_eq!(res, Ok(3));
+            assert_eq!(calls, 3);
+        });
+    }
+
+    #[test]
+    fn test_retry_exhausted() {
+        block_on(async {
+            let mut calls = 0;
+            let res: Result<(), i32> = retry(4, || {
+                calls += 1;
+                let attempt = calls;
+                async move { Err(attempt) }
+            })
+            .await;
+            assert_eq!(res, Err(4));
+            assert_eq!(calls, 4);
+
+            calls = 0;
+            let res: Result<(), i32> = retry(0, || {
+                calls += 1;
+                async { Err(0) }
+            })
+            .await;
+            assert_eq!(res, Err(0));
+            assert_eq!(calls, 1);
+        });
+    }
+
+    #[test]
+    fn test_retry_if_short_circuits() {
+        block_on(async {
+            let mut calls = 0;
+            let res: Result<(), u16> = retry_if(
+                5,
+                |status| *status == 503,
+                || {
+                    calls += 1;
+                    async { Err(400) }
+                },
+            )
+            .await;
+            assert_eq!(res, Err(400));
+            assert_eq!(calls, 1);
+        });
+    }
+
+    #[test]
+    fn test_retry_if_retries_matching_errors() {
+        block_on(async {
+            let mut calls = 0;
+            let res = retry_if(
+                5,
+                |status| *status == 503 || *status == 429,
+                || {
+                    calls += 1;
+                    let status = [503, 429, 200][calls - 1];
+                    async move {
+                        if status == 200 {
+                            Ok(status)
+                        } else {
+                            Err(status)
+                        }
+                    }
+                },
+            )
+            .await;
+            assert_eq!(res, Ok(200));
+            assert_eq!(calls, 3);
+
+            calls = 0;
+            let res: Result<(), u16> = retry_if(
+                3,
+                |status| *status == 503,
+                || {
+                    calls += 1;
+                    async { Err(503) }
+                },
+            )
+            .await;
+            assert_eq!(res, Err(503));
+            assert_eq!(calls, 3);
+        });
+    }
+
+    #[test]
+    fn test_retry_with_backoff_schedule() {
+        block_on(async {
+            let mut delays = Vec::new();
+            let mut calls = 0;
+            let policy = Backoff::new()
+                .attempts(6)
+                .initial(Duration::from_millis(10))
+                .max(Duration::from_millis(50))
+                .multiplier(2.0);
+            let res: Result<(), usize> = retry_with_backoff(
+                policy,
+                |delay| {
+                    delays.push(delay);
+                    async {}
+                },
+                || {
+                    calls += 1;
+                    let attempt = calls;
+                    async move { Err(attempt) }
+                },
+            )
+            .await;
+            assert_eq!(res, Err(6));
+            assert_eq!(
+                delays,
+                [10, 20, 40, 50, 50].map(Duration::from_millis),
+                "delays double and are capped at max"
+            );
+        });
+    }
+
+    #[test]
+    fn test_retry_with_backoff_success_and_jitter() {
+        block_on(async {
+            let mut delays = Vec::new();
+            let mut calls = 0;
+            let policy = Backoff::new()
+                .attempts(5)
+                .initial(Duration::from_millis(100))
+                .jitter(|delay| delay / 2);
+            let res = retry_with_backoff(
+                policy,
+                |delay| {
+                    delays.push(delay);
+                    async {}
+                },
+                || {
+                    calls += 1;
+                    let attempt = calls;
+                    async move {
+                        if attempt < 3 {
+                            Err(attempt)
+                        } else {
+                            Ok(attempt)
+                        }
+                    }
+                },
+            )
+            .await;
+            assert_eq!(res, Ok(3));
+            assert_eq!(delays, [50, 100].map(Duration::from_millis));
+        });
+    }
+
+    #[test]
+    fn test_cancel_retry_stops_attempts() {
+        let mut calls = 0;
+        {
+            let mut fut = pin!(retry(5, || {
+                calls += 1;
+                async {
+                    yield_now().await;
+                    Err::<(), _>("transient")
+                }
+            }));
+            assert!(poll_once(fut.as_mut()).is_pending());
+            assert!(poll_once(fut.as_mut()).is_pending());
+        }
+        assert_eq!(calls, 2);
+    }
+
+    #[test]
+    fn test_cancel_retry_with_backoff_during_sleep() {
+        let mut calls = 0;
+        let mut sleeps = 0;
+        {
+            let mut fut = pin!(retry_with_backoff(
+                Backoff::new().attempts(3),
+                |_| {
+                    sleeps += 1;
+                    yield_now()
+                },
+                || {
+                    calls += 1;
+                    async { Err::<(), _>("unavailable") }
+                },
+            ));
+            assert!(poll_once(fut.as_mut()).is_pending());
+        }
+        assert_eq!((calls, sleeps), (1, 1));
+    }
+}
@@ -0,0 +1,187 @@
+//! Running combinator work on spawned [`tokio`] tasks.
+//!
+//! Requires the `tokio` feature, which enables Tokio's `rt` and `rt-multi-thread`
+//! features.
+
+use core::future::Future;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+
+use pin_project_lite::pin_project;
+use tokio::task::{JoinError, JoinHandle};
+
+use crate::state::{State, Step};
+
+/// Extensions for [`Result<T, E>`] that run async work on a Tokio task.
+pub trait AsyncResultSpawnExt<T, E> {
+    /// Like [`async_and_then`](crate::AsyncResultExt::async_and_then), but the
+    /// future returned by `op` runs on a task spawned with [`tokio::spawn`].
+    ///
+    /// `op` itself is called on the first poll, on the polling thread; only its
+    /// future is spawned. If the task panics or is cancelled, `on_join_error`
+    /// converts the [`JoinError`] into the error type. If the result is `Err`,
+    /// nothing is called or spawned.
+    ///
+    /// Spawning imposes `Send + 'static` on the future and its output.
+    ///
+    /// # Cancellation
+    ///
+    /// Dropping the returned future after its first poll detaches the spawned task
+    /// rather than cancelling it: `op`'s future still runs to completion and its
+    /// output is discarded. Dropping it before the first poll spawns nothing.
+    ///
+    /// # Panics
+    ///
+    /// Panics if polled outside of a Tokio runtime while the result is `Ok`.
+    ///
+    /// ```
+    /// use async_result_ext::spawn::AsyncResultSpawnExt;
+    ///
+    /// # tokio::runtime::Runtime::new().unwrap().block_on(async {
+    /// let r: Result<u64, String> = Ok(10);
+    /// let res = r
+    ///     .async_and_then_spawn(
+    ///         |n| async move { Ok((1..=n).product::<u64>()) },
+    ///         |join_err| join_err.to_string(),
+    ///     )
+    ///     .await;
+    /// assert_eq!(res, Ok(3628800));
+    /// # });
+    /// ```
+    fn async_and_then_spawn<U, F, Fut, J>(
+        self,
+        op: F,
+        on_join_error: J,
+    ) -> AsyncAndThenSpawn<T, E, U, F, J>
+    where
+        F: FnOnce(T) -> Fut,
+        Fut: Future<Output = Result<U, E>> + Send + 'static,
+        U: Send + 'static,
+        E: Send + 'static,
+        J: FnOnce(JoinError) -> E;
+}
+
+impl<T, E> AsyncResultSpawnExt<T, E> for Result<T, E> {
+    fn async_and_then_spawn<U, F, Fut, J>(
+        self,
+        op: F,
+        on_join_error: J,
+    ) -> AsyncAndThenSpawn<T, E, U, F, J>
+    where
+        F: FnOnce(T) -> Fut,
+        Fut: Future<Output = Result<U, E>> + Send + 'static,
+        U: Send + 'static,
+        E: Send + 'static,
+        J: FnOnce(JoinError) -> E,
+    {
+        AsyncAndThenSpawn {
+            state: State::new((self, op, on_join_error)),
+        }
+    }
+}
+
+pin_project! {
+    /// Future for the [`async_and_then_spawn`](AsyncResultSpawnExt::async_and_then_spawn) method.
+    #[must_use = "futures do nothing unless you `.await` or poll them"]
+    pub struct AsyncAndThenSpawn<T, E, U, F, J> {
+        #[pin]
+        state: State<(Result<T, E>, F, J), JoinHandle<Result<U, E>>, J>,
+    }
+}
+
+impl<T, E, U, F, Fut, J> Future for AsyncAndThenSpawn<T, E, U, F, J>
+where
+    F: FnOnce(T) -> Fut,
+    Fut: Future<Output = Result<U, E>> + Send + 'static,
+    U: Send + 'static,
+    E: Send + 'static,
+    J: FnOnce(JoinError) -> E,
+{
+    type Output = Result<U, E>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        self.project().state.poll_with(
+            "async_and_then_spawn",
+            cx,
+            |(this, op, on_join_error)| match this {
+                Ok(value) => Step::Run(tokio::spawn(op(value)), on_join_error),
+                Err(err) => Step::Ready(Err(err)),
+            },
+            |joined, on_join_error| joined.unwrap_or_else(|err| Err(on_join_error(err))),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::string::{String, ToString};
+    use std::thread;
+
+    fn multi_thread_runtime() -> tokio::runtime::Runtime {
+        tokio::runtime::Builder::new_multi_thread()
+            .worker_threads(2)
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_async_and_then_spawn() {
+        let caller = thread::current().id();
+        let res = multi_thread_runtime().block_on(async {
+            Ok::<_, String>(2)
+                .async_and_then_spawn(
+                    |v| async move { Ok((v * 2, thread::current().id())) },
+                    |err| err.to_string(),
+                )
+                .await
+        });
+        let (value, worker) = res.unwrap();
+        assert_eq!(value, 4);
+        assert_ne!(worker, caller);
+    }
+
+    #[test]
+    fn test_async_and_then_spawn_errors() {
+        multi_thread_runtime().block_on(async {
+            let r: Result<i32, String> = Ok(1);
+            let res: Result<i32, String> = r
+                .async_and_then_spawn(
+                    |_| async { panic!("task failed") },
+                    |_| "joined".to_string(),
+                )
+                .await;
+            assert_eq!(res, Err("joined".to_string()));
+
+            let r: Result<i32, String> = Err("fail".to_string());
+            let res: Result<i32, String> = r
+                .async_and_then_spawn(
+                    |_| -> std::future::Ready<Result<i32, String>> { unreachable!() },
+                    |_| unreachable!(),
+                )
+                .await;
+            assert_eq!(res, Err("fail".to_string()));
+        });
+    }
+
+    #[test]
+    fn test_cancel_async_and_then_spawn_detaches() {
+        let (tx, rx) = std::sync::mpsc::channel();
+        multi_thread_runtime().block_on(async {
+            {
+                let mut fut = core::pin::pin!(Ok::<_, String>(()).async_and_then_spawn(
+                    |()| async move {
+                        tokio::task::yield_now().await;
+                        tx.send("finished").unwrap();
+                        Ok(())
+                    },
+                    |err| err.to_string(),
+                ));
+                assert!(crate::tests::poll_once(fut.as_mut()).is_pending());
+            }
+            // The runtime is still alive, so the detached task can finish.
+            let finished = rx.recv_timeout(std::time::Duration::from_secs(5));
+            assert_eq!(finished, Ok("finished"));
+        });
+    }
+}
@@ -0,0 +1,111 @@
+use core::future::Future;
+use core::pin::Pin;
+use core::task::{Context, Poll, ready};
+
+use pin_project_lite::pin_project;
+
+pin_project! {
+    /// Lifecycle shared by the named futures of this crate.
+    ///
+    /// `Init` holds the captured input until the first poll, `Running` drives the
+    /// future built from it (keeping any context needed to produce the output),
+    /// and `Done` marks completion.
+    #[project = StateProj]
+    #[project_replace = StateReplace]
+    pub(crate) enum State<I, Fut, C> {
+        Init { init: I },
+        Running { #[pin] fut: Fut, ctx: C },
+        Done,
+    }
+}
+
+/// What to do after inspecting the captured input on the first poll.
+pub(crate) enum Step<Fut, C, O> {
+    /// Drive `Fut`, then combine its output with `C`.
+    Run(Fut, C),
+    /// Complete immediately without awaiting anything.
+    Ready(O),
+}
+
+impl<I, Fut, C> State<I, Fut, C> {
+    pub(crate) fn new(init: I) -> Self {
+        State::Init { init }
+    }
+}
+
+impl<I, Fut: Future, C> State<I, Fut, C> {
+    /// Polls the state machine.
+    ///
+    /// `start` runs once, on the first poll, and decides whether there is a future
+    /// to drive. `finish` runs once that future completes. `name` identifies the
+    /// combinator if the future is polled again after completion.
+    pub(crate) fn poll_with<O>(
+        mut self: Pin<&mut Self>,
+        name: &'static str,
+        cx: &mut Context<'_>,
+        start: impl FnOnce(I) -> Step<Fut, C, O>,
+        finish: impl FnOnce(Fut::Output, C) -> O,
+    ) -> Poll<O> {
+        if let StateProj::Init { .. } = self.as_mut().project() {
+            let StateReplace::Init { init } = self.as_mut().project_replace(State::Done) else {
+                unreachable!()
+            };
+            match start(init) {
+                Step::Run(fut, ctx) => self.set(State::Running { fut, ctx }),
+                Step::Ready(output) => return Poll::Ready(output),
+            }
+        }
+
+        match self.as_mut().project() {
+            StateProj::Running { fut, .. } => {
+                let output = ready!(fut.poll(cx));
+                let StateReplace::Running { ctx, .. } = self.project_replace(State::Done) else {
+                    unreachable!()
+                };
+                Poll::Ready(finish(output, ctx))
+            }
+            StateProj::Init { .. } => unreachable!(),
+            StateProj::Done => polled_after_completion(name),
+        }
+    }
+}
+
+/// Panics for a future polled again after it returned `Poll::Ready`.
+///
+/// Debug builds name the combinator, e.g. "async_map future polled after
+/// completion"; release builds keep the message generic.
+#[cold]
+#[track_caller]
+pub(crate) fn polled_after_completion(name: &'static str) -> ! {
+    if cfg!(debug_assertions) {
+        panic!("{name} future polled after completion")
+    } else {
+        panic!("future polled after completion")
+    }
+}
+
+pin_project! {
+    /// One of two futures, used when a combinator awaits a different future per branch.
+    #[project = EitherProj]
+    pub(crate) enum Either<A, B> {
+        Left { #[pin] fut: A },
+        Right { #[pin] fut: B },
+    }
+}
+
+/// Output of an [`Either`] future, remembering which branch produced it.
+pub(crate) enum EitherOutput<A, B> {
+    Left(A),
+    Right(B),
+}
+
+impl<A: Future, B: Future> Future for Either<A, B> {
+    type Output = EitherOutput<A::Output, B::Output>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        match self.project() {
+            EitherProj::Left { fut } => fut.poll(cx).map(EitherOutput::Left),
+            EitherProj::Right { fut } => fut.poll(cx).map(EitherOutput::Right),
+        }
+    }
+}
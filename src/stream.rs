@@ -0,0 +1,907 @@
+//! Async extensions for streams of [`Result`]s and the named streams they return.
+//!
+//! Requires the `stream` feature. Built on [`futures_core::Stream`], so any
+//! stream from the `futures` ecosystem can be used. [`iter_async_map`] builds
+//! such a stream from an iterator.
+
+use core::future::Future;
+use core::pin::Pin;
+use core::task::{Context, Poll, ready};
+
+use futures_core::Stream;
+use pin_project_lite::pin_project;
+
+use crate::state::polled_after_completion;
+
+/// Asynchronous extensions for streams yielding [`Result<T, E>`].
+///
+/// ```
+/// use async_result_ext::stream::AsyncTryStreamExt;
+/// use futures::stream::{self, StreamExt};
+///
+/// # futures::executor::block_on(async {
+/// let pages = stream::iter([Ok(1), Err("rate limited"), Ok(3)]);
+/// let doubled: Vec<_> = pages.async_map_ok(|v| async move { v * 2 }).collect().await;
+/// assert_eq!(doubled, [Ok(2), Err("rate limited"), Ok(6)]);
+/// # });
+/// ```
+///
+/// # Cancellation
+///
+/// The returned streams and futures own the underlying stream, so dropping
+/// them drops it too, along with the pending closure future. An item whose
+/// closure future has not completed is lost; awaiting `next()` on one of the
+/// returned streams is otherwise safe to cancel, because that item stays
+/// pending inside the stream until the next poll.
+pub trait AsyncTryStreamExt<T, E>: Stream<Item = Result<T, E>> {
+    /// Applies an async function `op` to every `Ok` item.
+    ///
+    /// `Err` items pass through unchanged, and the item order is preserved.
+    fn async_map_ok<U, F, Fut>(self, op: F) -> AsyncMapOk<Self, F, Fut>
+    where
+        Self: Sized,
+        F: FnMut(T) -> Fut,
+        Fut: Future<Output = U>;
+
+    /// Applies an async function `op` to every `Err` item.
+    ///
+    /// `Ok` items pass through unchanged, and the item order is preserved.
+    fn async_map_err<O, F, Fut>(self, op: F) -> AsyncMapErr<Self, F, Fut>
+    where
+        Self: Sized,
+        F: FnMut(E) -> Fut,
+        Fut: Future<Output = O>;
+
+    /// Awaits `op` on every `Ok` item for its side effects.
+    ///
+    /// Stops at the first error, whether it is an `Err` item from the stream or
+    /// an `Err` returned by `op`; the rest of the stream is not polled.
+    ///
+    /// ```
+    /// use async_result_ext::stream::AsyncTryStreamExt;
+    /// use futures::stream;
+    ///
+    /// # futures::executor::block_on(async {
+    /// let mut total = 0;
+    /// let res = stream::iter([Ok::<_, &str>(1), Ok(2)])
+    ///     .async_try_for_each(|v| {
+    ///         total += v;
+    ///         async { Ok(()) }
+    ///     })
+    ///     .await;
+    /// assert_eq!(res, Ok(()));
+    /// assert_eq!(total, 3);
+    /// # });
+    /// ```
+    fn async_try_for_each<F, Fut>(self, op: F) -> AsyncTryForEach<Self, F, Fut>
+    where
+        Self: Sized,
+        F: FnMut(T) -> Fut,
+        Fut: Future<Output = Result<(), E>>;
+
+    /// Applies an async function `op` to `Ok` items until it returns `None`.
+    ///
+    /// Each `Some` output is yielded as `Ok`, and `Err` items pass through
+    /// unchanged. Once `op` returns `None`, the stream ends: that item is
+    /// dropped and the underlying stream is not polled again.
+    ///
+    /// ```
+    /// use async_result_ext::stream::AsyncTryStreamExt;
+    /// use futures::stream::{self, StreamExt};
+    ///
+    /// # futures::executor::block_on(async {
+    /// let lines = stream::iter([Ok("a"), Err("bad utf-8"), Ok("b"), Ok(""), Ok("c")]);
+    /// let header: Vec<_> = lines
+    ///     .async_map_while(|line| async move { (!line.is_empty()).then_some(line) })
+    ///     .collect()
+    ///     .await;
+    /// assert_eq!(header, [Ok("a"), Err("bad utf-8"), Ok("b")]);
+    /// # });
+    /// ```
+    fn async_map_while<U, F, Fut>(self, op: F) -> AsyncMapWhile<Self, F, Fut>
+    where
+        Self: Sized,
+        F: FnMut(T) -> Fut,
+        Fut: Future<Output = Option<U>>;
+
+    /// Threads the state `init` through an async step over every item.
+    ///
+    /// The async counterpart of [`Iterator::scan`]: `op` receives the state
+    /// mutably along with each item, `Ok` or `Err`, and its future yields the next
+    /// output. Once a future returns `None`, the stream ends and the underlying
+    /// stream is not polled again. Since the future can't borrow the state, `op`
+    /// updates it before returning the future.
+    ///
+    /// ```
+    /// use async_result_ext::stream::AsyncTryStreamExt;
+    /// use futures::stream::{self, StreamExt};
+    ///
+    /// # futures::executor::block_on(async {
+    /// let deposits = stream::iter([Ok(10), Ok(5), Err("declined"), Ok(20)]);
+    /// let balances: Vec<_> = deposits
+    ///     .async_scan(0, |balance, deposit| {
+    ///         let res = deposit.map(|amount| {
+    ///             *balance += amount;
+    ///             *balance
+    ///         });
+    ///         async move { res.ok() }
+    ///     })
+    ///     .collect()
+    ///     .await;
+    /// assert_eq!(balances, [10, 15]);
+    /// # });
+    /// ```
+    fn async_scan<A, U, F, Fut>(self, init: A, op: F) -> AsyncScan<Self, A, F, Fut>
+    where
+        Self: Sized,
+        F: FnMut(&mut A, Result<T, E>) -> Fut,
+        Fut: Future<Output = Option<U>>;
+
+    /// Drops `Err` items equal to the error right before them, awaiting `op` on
+    /// each one dropped.
+    ///
+    /// The first error of a run of equal errors is yielded and the repeats are
+    /// handed to `op`, e.g. to count them. `Ok` items pass through unchanged and
+    /// end the run. The last yielded error is kept for comparison, which is why
+    /// `E` must be [`Clone`].
+    ///
+    /// ```
+    /// use async_result_ext::stream::AsyncTryStreamExt;
+    /// use futures::stream::{self, StreamExt};
+    ///
+    /// # futures::executor::block_on(async {
+    /// let mut suppressed = 0;
+    /// let polls = stream::iter([Err("offline"), Err("offline"), Ok(1), Err("offline")]);
+    /// let items: Vec<_> = polls
+    ///     .async_dedup_err(|_| {
+    ///         suppressed += 1;
+    ///         async {}
+    ///     })
+    ///     .collect()
+    ///     .await;
+    /// assert_eq!(items, [Err("offline"), Ok(1), Err("offline")]);
+    /// assert_eq!(suppressed, 1);
+    /// # });
+    /// ```
+    fn async_dedup_err<F, Fut>(self, op: F) -> AsyncDedupErr<Self, E, F, Fut>
+    where
+        Self: Sized,
+        E: Clone + PartialEq,
+        F: FnMut(E) -> Fut,
+        Fut: Future<Output = ()>;
+}
+
+impl<S, T, E> AsyncTryStreamExt<T, E> for S
+where
+    S: Stream<Item = Result<T, E>>,
+{
+    fn async_map_ok<U, F, Fut>(self, op: F) -> AsyncMapOk<Self, F, Fut>
+    where
+        Self: Sized,
+        F: FnMut(T) -> Fut,
+        Fut: Future<Output = U>,
+    {
+        AsyncMapOk {
+            stream: self,
+            op,
+            pending: None,
+        }
+    }
+
+    fn async_map_err<O, F, Fut>(self, op: F) -> AsyncMapErr<Self, F, Fut>
+    where
+        Self: Sized,
+        F: FnMut(E) -> Fut,
+        Fut: Future<Output = O>,
+    {
+        AsyncMapErr {
+            stream: self,
+            op,
+            pending: None,
+        }
+    }
+
+    fn async_try_for_each<F, Fut>(self, op: F) -> AsyncTryForEach<Self, F, Fut>
+    where
+        Self: Sized,
+        F: FnMut(T) -> Fut,
+        Fut: Future<Output = Result<(), E>>,
+    {
+        AsyncTryForEach {
+            stream: self,
+            op,
+            pending: None,
+            done: false,
+        }
+    }
+
+    fn async_map_while<U, F, Fut>(self, op: F) -> AsyncMapWhile<Self, F, Fut>
+    where
+        Self: Sized,
+        F: FnMut(T) -> Fut,
+        Fut: Future<Output = Option<U>>,
+    {
+        AsyncMapWhile {
+            stream: self,
+            op,
+            pending: None,
+            done: false,
+        }
+    }
+
+    fn async_scan<A, U, F, Fut>(self, init: A, op: F) -> AsyncScan<Self, A, F, Fut>
+    where
+        Self: Sized,
+        F: FnMut(&mut A, Result<T, E>) -> Fut,
+        Fut: Future<Output = Option<U>>,
+    {
+        AsyncScan {
+            stream: self,
+            state: init,
+            op,
+            pending: None,
+            done: false,
+        }
+    }
+
+    fn async_dedup_err<F, Fut>(self, op: F) -> AsyncDedupErr<Self, E, F, Fut>
+    where
+        Self: Sized,
+        E: Clone + PartialEq,
+        F: FnMut(E) -> Fut,
+        Fut: Future<Output = ()>,
+    {
+        AsyncDedupErr {
+            stream: self,
+            op,
+            last: None,
+            pending: None,
+        }
+    }
+}
+
+pin_project! {
+    /// Stream for the [`async_map_ok`](AsyncTryStreamExt::async_map_ok) method.
+    #[must_use = "streams do nothing unless polled"]
+    pub struct AsyncMapOk<S, F, Fut> {
+        #[pin]
+        stream: S,
+        op: F,
+        #[pin]
+        pending: Option<Fut>,
+    }
+}
+
+impl<S, T, E, U, F, Fut> Stream for AsyncMapOk<S, F, Fut>
+where
+    S: Stream<Item = Result<T, E>>,
+    F: FnMut(T) -> Fut,
+    Fut: Future<Output = U>,
+{
+    type Item = Result<U, E>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let mut this = self.project();
+        loop {
+            if let Some(fut) = this.pending.as_mut().as_pin_mut() {
+                let output = ready!(fut.poll(cx));
+                this.pending.set(None);
+                return Poll::Ready(Some(Ok(output)));
+            }
+
+            match ready!(this.stream.as_mut().poll_next(cx)) {
+                Some(Ok(value)) => this.pending.set(Some((this.op)(value))),
+                Some(Err(err)) => return Poll::Ready(Some(Err(err))),
+                None => return Poll::Ready(None),
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let pending = usize::from(self.pending.is_some());
+        let (lower, upper) = self.stream.size_hint();
+        (
+            lower.saturating_add(pending),
+            upper.and_then(|upper| upper.checked_add(pending)),
+        )
+    }
+}
+
+pin_project! {
+    /// Stream for the [`async_map_err`](AsyncTryStreamExt::async_map_err) method.
+    #[must_use = "streams do nothing unless polled"]
+    pub struct AsyncMapErr<S, F, Fut> {
+        #[pin]
+        stream: S,
+        op: F,
+        #[pin]
+        pending: Option<Fut>,
+    }
+}
+
+impl<S, T, E, O, F, Fut> Stream for AsyncMapErr<S, F, Fut>
+where
+    S: Stream<Item = Result<T, E>>,
+    F: FnMut(E) -> Fut,
+    Fut: Future<Output = O>,
+{
+    type Item = Result<T, O>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let mut this = self.project();
+        loop {
+            if let Some(fut) = this.pending.as_mut().as_pin_mut() {
+                let output = ready!(fut.poll(cx));
+                this.pending.set(None);
+                return Poll::Ready(Some(Err(output)));
+            }
+
+            match ready!(this.stream.as_mut().poll_next(cx)) {
+                Some(Ok(value)) => return Poll::Ready(Some(Ok(value))),
+                Some(Err(err)) => this.pending.set(Some((this.op)(err))),
+                None => return Poll::Ready(None),
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let pending = usize::from(self.pending.is_some());
+        let (lower, upper) = self.stream.size_hint();
+        (
+            lower.saturating_add(pending),
+            upper.and_then(|upper| upper.checked_add(pending)),
+        )
+    }
+}
+
+pin_project! {
+    /// Future for the [`async_try_for_each`](AsyncTryStreamExt::async_try_for_each) method.
+    #[must_use = "futures do nothing unless you `.await` or poll them"]
+    pub struct AsyncTryForEach<S, F, Fut> {
+        #[pin]
+        stream: S,
+        op: F,
+        #[pin]
+        pending: Option<Fut>,
+        done: bool,
+    }
+}
+
+impl<S, T, E, F, Fut> Future for AsyncTryForEach<S, F, Fut>
+where
+    S: Stream<Item = Result<T, E>>,
+    F: FnMut(T) -> Fut,
+    Fut: Future<Output = Result<(), E>>,
+{
+    type Output = Result<(), E>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let mut this = self.project();
+        if *this.done {
+            polled_after_completion("async_try_for_each");
+        }
+
+        loop {
+            if let Some(fut) = this.pending.as_mut().as_pin_mut() {
+                let output = ready!(fut.poll(cx));
+                this.pending.set(None);
+                if let Err(err) = output {
+                    *this.done = true;
+                    return Poll::Ready(Err(err));
+                }
+            }
+
+            let output = match ready!(this.stream.as_mut().poll_next(cx)) {
+                Some(Ok(value)) => {
+                    this.pending.set(Some((this.op)(value)));
+                    continue;
+                }
+                Some(Err(err)) => Err(err),
+                None => Ok(()),
+            };
+            *this.done = true;
+            return Poll::Ready(output);
+        }
+    }
+}
+
+pin_project! {
+    /// Stream for the [`async_map_while`](AsyncTryStreamExt::async_map_while) method.
+    #[must_use = "streams do nothing unless polled"]
+    pub struct AsyncMapWhile<S, F, Fut> {
+        #[pin]
+        stream: S,
+        op: F,
+        #[pin]
+        pending: Option<Fut>,
+        done: bool,
+    }
+}
+
+impl<S, T, E, U, F, Fut> Stream for AsyncMapWhile<S, F, Fut>
+where
+    S: Stream<Item = Result<T, E>>,
+    F: FnMut(T) -> Fut,
+    Fut: Future<Output = Option<U>>,
+{
+    type Item = Result<U, E>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let mut this = self.project();
+        if *this.done {
+            return Poll::Ready(None);
+        }
+        loop {
+            if let Some(fut) = this.pending.as_mut().as_pin_mut() {
+                let output = ready!(fut.poll(cx));
+                this.pending.set(None);
+                return Poll::Ready(match output {
+                    Some(output) => Some(Ok(output)),
+                    None => {
+                        *this.done = true;
+                        None
+                    }
+                });
+            }
+
+            match ready!(this.stream.as_mut().poll_next(cx)) {
+                Some(Ok(value)) => this.pending.set(Some((this.op)(value))),
+                Some(Err(err)) => return Poll::Ready(Some(Err(err))),
+                None => {
+                    *this.done = true;
+                    return Poll::Ready(None);
+                }
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if self.done {
+            return (0, Some(0));
+        }
+        let pending = usize::from(self.pending.is_some());
+        let (_, upper) = self.stream.size_hint();
+        (0, upper.and_then(|upper| upper.checked_add(pending)))
+    }
+}
+
+pin_project! {
+    /// Stream for the [`async_scan`](AsyncTryStreamExt::async_scan) method.
+    #[must_use = "streams do nothing unless polled"]
+    pub struct AsyncScan<S, A, F, Fut> {
+        #[pin]
+        stream: S,
+        state: A,
+        op: F,
+        #[pin]
+        pending: Option<Fut>,
+        done: bool,
+    }
+}
+
+impl<S, T, E, A, U, F, Fut> Stream for AsyncScan<S, A, F, Fut>
+where
+    S: Stream<Item = Result<T, E>>,
+    F: FnMut(&mut A, Result<T, E>) -> Fut,
+    Fut: Future<Output = Option<U>>,
+{
+    type Item = U;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let mut this = self.project();
+        if *this.done {
+            return Poll::Ready(None);
+        }
+        if this.pending.is_none() {
+            match ready!(this.stream.as_mut().poll_next(cx)) {
+                Some(item) => this.pending.set(Some((this.op)(this.state, item))),
+                None => {
+                    *this.done = true;
+                    return Poll::Ready(None);
+                }
+            }
+        }
+
+        let fut = this
+            .pending
+            .as_mut()
+            .as_pin_mut()
+            .expect("future set above");
+        let output = ready!(fut.poll(cx));
+        this.pending.set(None);
+        *this.done = output.is_none();
+        Poll::Ready(output)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if self.done {
+            return (0, Some(0));
+        }
+        let pending = usize::from(self.pending.is_some());
+        let (_, upper) = self.stream.size_hint();
+        (0, upper.and_then(|upper| upper.checked_add(pending)))
+    }
+}
+
+pin_project! {
+    /// Stream for the [`async_dedup_err`](AsyncTryStreamExt::async_dedup_err) method.
+    #[must_use = "streams do nothing unless polled"]
+    pub struct AsyncDedupErr<S, E, F, Fut> {
+        #[pin]
+        stream: S,
+        op: F,
+        last: Option<E>,
+        #[pin]
+        pending: Option<Fut>,
+    }
+}
+
+impl<S, T, E, F, Fut> Stream for AsyncDedupErr<S, E, F, Fut>
+where
+    S: Stream<Item = Result<T, E>>,
+    E: Clone + PartialEq,
+    F: FnMut(E) -> Fut,
+    Fut: Future<Output = ()>,
+{
+    type Item = Result<T, E>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let mut this = self.project();
+        loop {
+            if let Some(fut) = this.pending.as_mut().as_pin_mut() {
+                ready!(fut.poll(cx));
+                this.pending.set(None);
+            }
+
+            match ready!(this.stream.as_mut().poll_next(cx)) {
+                Some(Ok(value)) => {
+                    *this.last = None;
+                    return Poll::Ready(Some(Ok(value)));
+                }
+                Some(Err(err)) if this.last.as_ref() == Some(&err) => {
+                    this.pending.set(Some((this.op)(err)));
+                }
+                Some(Err(err)) => {
+                    *this.last = Some(err.clone());
+                    return Poll::Ready(Some(Err(err)));
+                }
+                None => return Poll::Ready(None),
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (_, upper) = self.stream.size_hint();
+        (0, upper)
+    }
+}
+
+/// Turns `iter` into a stream by awaiting `op` on one item at a time.
+///
+/// Items are pulled from the iterator lazily: the next item is taken, and `op`
+/// called on it, only when the stream is polled after the previous future
+/// completed. Nothing is collected up front, so a slow consumer holds back the
+/// iterator.
+///
+/// ```
+/// use async_result_ext::stream::iter_async_map;
+/// use futures::stream::StreamExt;
+///
+/// # futures::executor::block_on(async {
+/// let squares: Vec<_> = iter_async_map(1..=3, |v| async move { v * v }).collect().await;
+/// assert_eq!(squares, [1, 4, 9]);
+/// # });
+/// ```
+pub fn iter_async_map<I, U, F, Fut>(iter: I, op: F) -> IterAsyncMap<I::IntoIter, F, Fut>
+where
+    I: IntoIterator,
+    F: FnMut(I::Item) -> Fut,
+    Fut: Future<Output = U>,
+{
+    IterAsyncMap {
+        iter: iter.into_iter(),
+        op,
+        pending: None,
+    }
+}
+
+pin_project! {
+    /// Stream for the [`iter_async_map`] function.
+    #[must_use = "streams do nothing unless polled"]
+    pub struct IterAsyncMap<I, F, Fut> {
+        iter: I,
+        op: F,
+        #[pin]
+        pending: Option<Fut>,
+    }
+}
+
+impl<I, U, F, Fut> Stream for IterAsyncMap<I, F, Fut>
+where
+    I: Iterator,
+    F: FnMut(I::Item) -> Fut,
+    Fut: Future<Output = U>,
+{
+    type Item = U;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let mut this = self.project();
+        if this.pending.is_none() {
+            match this.iter.next() {
+                Some(item) => this.pending.set(Some((this.op)(item))),
+                None => return Poll::Ready(None),
+            }
+        }
+
+        let fut = this
+            .pending
+            .as_mut()
+            .as_pin_mut()
+            .expect("future set above");
+        let output = ready!(fut.poll(cx));
+        this.pending.set(None);
+        Poll::Ready(Some(output))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let pending = usize::from(self.pending.is_some());
+        let (lower, upper) = self.iter.size_hint();
+        (
+            lower.saturating_add(pending),
+            upper.and_then(|upper| upper.checked_add(pending)),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::{poll_once, yield_now};
+    use core::pin::pin;
+    use futures::executor::block_on;
+    use futures::stream::{self, StreamExt};
+    use std::vec::Vec;
+
+    #[test]
+    fn test_async_map_ok() {
+        block_on(async {
+            let items = stream::iter([Ok(1), Err("a"), Ok(2), Ok(3), Err("b")]);
+            let mapped: Vec<_> = items
+                .async_map_ok(|v| async move { v * 10 })
+                .collect()
+                .await;
+            assert_eq!(mapped, [Ok(10), Err("a"), Ok(20), Ok(30), Err("b")]);
+        });
+    }
+
+    #[test]
+    fn test_async_map_ok_only_calls_op_for_ok() {
+        block_on(async {
+            let mut seen = Vec::new();
+            let items = stream::iter([Err("a"), Ok(1), Err("b")]);
+            let mapped: Vec<Result<i32, &str>> = items
+                .async_map_ok(|v| {
+                    seen.push(v);
+                    async move { v }
+                })
+                .collect()
+                .await;
+            assert_eq!(mapped, [Err("a"), Ok(1), Err("b")]);
+            assert_eq!(seen, [1]);
+        });
+    }
+
+    #[test]
+    fn test_async_map_err() {
+        block_on(async {
+            let mut seen = Vec::new();
+            let items = stream::iter([Ok(1), Err("io"), Ok(2), Err("parse")]);
+            let mapped: Vec<Result<i32, usize>> = items
+                .async_map_err(|e| {
+                    seen.push(e);
+                    async move { e.len() }
+                })
+                .collect()
+                .await;
+            assert_eq!(mapped, [Ok(1), Err(2), Ok(2), Err(5)]);
+            assert_eq!(seen, ["io", "parse"]);
+        });
+    }
+
+    #[test]
+    fn test_async_try_for_each_source_error() {
+        block_on(async {
+            let mut seen = Vec::new();
+            let items = stream::iter([Ok(1), Err("source"), Ok(3)]);
+            let res = items
+                .async_try_for_each(|v| {
+                    seen.push(v);
+                    async { Ok(()) }
+                })
+                .await;
+            assert_eq!(res, Err("source"));
+            assert_eq!(seen, [1]);
+        });
+    }
+
+    #[test]
+    fn test_async_try_for_each_closure_error() {
+        block_on(async {
+            let mut seen = Vec::new();
+            let items = stream::iter([Ok(1), Ok(2), Ok(3), Ok(4)]);
+            let res = items
+                .async_try_for_each(|v| {
+                    seen.push(v);
+                    async move { if v == 3 { Err("third") } else { Ok(()) } }
+                })
+                .await;
+            assert_eq!(res, Err("third"));
+            assert_eq!(seen, [1, 2, 3]);
+
+            let items = stream::iter([Ok::<i32, &str>(1), Ok(2)]);
+            assert_eq!(items.async_try_for_each(|_| async { Ok(()) }).await, Ok(()));
+        });
+    }
+
+    #[test]
+    fn test_async_map_while() {
+        block_on(async {
+            let mut seen = Vec::new();
+            let items = stream::iter([Ok(1), Err("a"), Ok(2), Ok(-1), Ok(3), Err("b")]);
+            {
+                let mut mapped = pin!(items.async_map_while(|v| {
+                    seen.push(v);
+                    async move {
+                        yield_now().await;
+                        (v > 0).then_some(v * 10)
+                    }
+                }));
+                let mut out = Vec::new();
+                while let Some(item) = mapped.next().await {
+                    out.push(item);
+                }
+                assert_eq!(out, [Ok(10), Err("a"), Ok(20)]);
+                assert_eq!(mapped.next().await, None);
+                assert_eq!(mapped.size_hint(), (0, Some(0)));
+            }
+            assert_eq!(seen, [1, 2, -1]);
+        });
+    }
+
+    #[test]
+    fn test_async_map_while_exhausted() {
+        block_on(async {
+            let items = stream::iter([Ok::<_, &str>(1), Ok(2)]);
+            let mapped: Vec<_> = items
+                .async_map_while(|v| async move { Some(v + 1) })
+                .collect()
+                .await;
+            assert_eq!(mapped, [Ok(2), Ok(3)]);
+        });
+    }
+
+    #[test]
+    fn test_async_scan_running_totals() {
+        block_on(async {
+            let items = stream::iter([Ok(1), Ok(2), Err("skip"), Ok(3)]);
+            let totals: Vec<_> = items
+                .async_scan(0, |sum, item| {
+                    let total = match item {
+                        Ok(v) => {
+                            *sum += v;
+                            Ok(*sum)
+                        }
+                        Err(e) => Err(e),
+                    };
+                    async move {
+                        yield_now().await;
+                        Some(total)
+                    }
+                })
+                .collect()
+                .await;
+            assert_eq!(totals, [Ok(1), Ok(3), Err("skip"), Ok(6)]);
+        });
+    }
+
+    #[test]
+    fn test_async_scan_stops_on_none() {
+        block_on(async {
+            let mut seen = Vec::new();
+            let items = stream::iter([Ok::<_, ()>(5), Ok(7), Ok(9), Ok(11)]);
+            {
+                let mut totals = pin!(items.async_scan(0, |sum, item| {
+                    let v = item.unwrap();
+                    seen.push(v);
+                    *sum += v;
+                    let sum = *sum;
+                    async move { (sum < 20).then_some(sum) }
+                }));
+                assert_eq!(totals.next().await, Some(5));
+                assert_eq!(totals.next().await, Some(12));
+                assert_eq!(totals.next().await, None);
+                assert_eq!(totals.next().await, None);
+            }
+            assert_eq!(seen, [5, 7, 9]);
+        });
+    }
+
+    #[test]
+    fn test_async_dedup_err() {
+        block_on(async {
+            let mut suppressed = Vec::new();
+            let items = stream::iter([
+                Err("a"),
+                Err("a"),
+                Err("a"),
+                Err("b"),
+                Err("a"),
+                Ok(1),
+                Ok(1),
+                Err("a"),
+                Err("a"),
+            ]);
+            let deduped: Vec<_> = items
+                .async_dedup_err(|e| {
+                    suppressed.push(e);
+                    yield_now()
+                })
+                .collect()
+                .await;
+            assert_eq!(
+                deduped,
+                [Err("a"), Err("b"), Err("a"), Ok(1), Ok(1), Err("a")]
+            );
+            assert_eq!(suppressed, ["a", "a", "a"]);
+        });
+    }
+
+    #[test]
+    fn test_cancel_next_keeps_pending_item() {
+        block_on(async {
+            let mut mapped = pin!(stream::iter([Ok::<_, ()>(1), Ok(2)]).async_map_ok(
+                |v| async move {
+                    yield_now().await;
+                    v * 10
+                }
+            ));
+            {
+                let mut next = mapped.next();
+                assert!(poll_once(Pin::new(&mut next)).is_pending());
+            }
+            assert_eq!(mapped.next().await, Some(Ok(10)));
+            assert_eq!(mapped.next().await, Some(Ok(20)));
+        });
+    }
+
+    #[test]
+    fn test_iter_async_map() {
+        block_on(async {
+            let mapped: Vec<_> = iter_async_map(["a", "bb", "ccc"], |s| async move {
+                yield_now().await;
+                s.len()
+            })
+            .collect()
+            .await;
+            assert_eq!(mapped, [1, 2, 3]);
+        });
+    }
+
+    #[test]
+    fn test_iter_async_map_is_lazy() {
+        block_on(async {
+            let mut produced = 0;
+            let mut called = 0;
+            let items = (1..=3).inspect(|_| produced += 1);
+            {
+                let mut mapped = pin!(iter_async_map(items, |v| {
+                    called += 1;
+                    async move { v * 2 }
+                }));
+                assert_eq!(mapped.next().await, Some(2));
+                assert_eq!(mapped.size_hint(), (2, Some(2)));
+            }
+            assert_eq!((produced, called), (1, 1));
+        });
+    }
+}
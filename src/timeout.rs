@@ -0,0 +1,245 @@
+//! Bounding fallible futures by a deadline.
+//!
+//! The deadline is any future that completes when time is up, such as
+//! `tokio::time::sleep(dur)`, so no timer or runtime is assumed.
+
+use core::fmt;
+use core::future::Future;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+
+use pin_project_lite::pin_project;
+
+use crate::state::polled_after_completion;
+
+/// Error of a [`timeout`]: either the deadline elapsed or the future failed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TimeoutOr<E> {
+    /// The deadline completed before the future.
+    Timeout,
+    /// The future completed first with this error.
+    Err(E),
+}
+
+impl<E> TimeoutOr<E> {
+    /// Returns the inner error, or the one produced by `on_timeout` if the deadline elapsed.
+    ///
+    /// This folds a timeout into the caller's own error type.
+    pub fn into_error(self, on_timeout: impl FnOnce() -> E) -> E {
+        match self {
+            TimeoutOr::Timeout => on_timeout(),
+            TimeoutOr::Err(err) => err,
+        }
+    }
+}
+
+impl<E: fmt::Display> fmt::Display for TimeoutOr<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TimeoutOr::Timeout => f.write_str("deadline elapsed"),
+            TimeoutOr::Err(err) => err.fmt(f),
+        }
+    }
+}
+
+impl<E: core::error::Error + 'static> core::error::Error for TimeoutOr<E> {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        match self {
+            TimeoutOr::Timeout => None,
+            TimeoutOr::Err(err) => Some(err),
+        }
+    }
+}
+
+/// Awaits `fut`, giving up with [`TimeoutOr::Timeout`] if `deadline` completes first.
+///
+/// `fut` is polled before `deadline`, so a future that is ready at the same time
+/// as the deadline still wins.
+///
+/// # Cancellation
+///
+/// Whichever future loses is dropped along with the returned future, at
+/// whatever await point it reached. On a timeout, `fut` must therefore be
+/// cancellation-safe. Dropping the returned future early drops both.
+///
+/// ```
+/// use async_result_ext::timeout::{TimeoutOr, timeout};
+///
+/// # futures::executor::block_on(async {
+/// let slow = core::future::pending::<Result<i32, &str>>();
+/// let res = timeout(slow, async {}).await;
+/// assert_eq!(res, Err(TimeoutOr::Timeout));
+///
+/// let res = res.map_err(|err| err.into_error(|| "timed out"));
+/// assert_eq!(res, Err("timed out"));
+/// # });
+/// ```
+pub fn timeout<T, E, Fut, S>(fut: Fut, deadline: S) -> Timeout<Fut, S>
+where
+    Fut: Future<Output = Result<T, E>>,
+    S: Future<Output = ()>,
+{
+    Timeout {
+        fut,
+        deadline,
+        done: false,
+    }
+}
+
+/// Like [`timeout`], but reports an elapsed deadline with the error from `on_timeout`.
+///
+/// This saves the [`TimeoutOr::into_error`] step when the timeout should share
+/// the work's own error type. `on_timeout` is only called if `deadline` wins.
+///
+/// # Cancellation
+///
+/// As with [`timeout`], the losing future is dropped at whatever await point it
+/// reached, so on a timeout `work` must be cancellation-safe.
+///
+/// ```
+/// use async_result_ext::timeout::race_timeout;
+///
+/// # futures::executor::block_on(async {
+/// let slow = core::future::pending::<Result<i32, &str>>();
+/// let res = race_timeout(slow, async {}, || "timed out").await;
+/// assert_eq!(res, Err("timed out"));
+/// # });
+/// ```
+pub async fn race_timeout<T, E, Fut, S, D>(work: Fut, deadline: S, on_timeout: D) -> Result<T, E>
+where
+    Fut: Future<Output = Result<T, E>>,
+    S: Future<Output = ()>,
+    D: FnOnce() -> E,
+{
+    timeout(work, deadline)
+        .await
+        .map_err(|err| err.into_error(on_timeout))
+}
+
+pin_project! {
+    /// Future for the [`timeout`] function.
+    #[must_use = "futures do nothing unless you `.await` or poll them"]
+    pub struct Timeout<Fut, S> {
+        #[pin]
+        fut: Fut,
+        #[pin]
+        deadline: S,
+        done: bool,
+    }
+}
+
+impl<T, E, Fut, S> Future for Timeout<Fut, S>
+where
+    Fut: Future<Output = Result<T, E>>,
+    S: Future<Output = ()>,
+{
+    type Output = Result<T, TimeoutOr<E>>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.project();
+        if *this.done {
+            polled_after_completion("timeout");
+        }
+
+        if let Poll::Ready(output) = this.fut.poll(cx) {
+            *this.done = true;
+            return Poll::Ready(output.map_err(TimeoutOr::Err));
+        }
+        if this.deadline.poll(cx).is_ready() {
+            *this.done = true;
+            return Poll::Ready(Err(TimeoutOr::Timeout));
+        }
+        Poll::Pending
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::{DropFlag, after, poll_once};
+    use core::cell::Cell;
+    use core::pin::pin;
+    use futures::executor::block_on;
+
+    #[test]
+    fn test_timeout_completes_first() {
+        block_on(async {
+            let fut = async {
+                after(2).await;
+                Ok::<_, &str>(7)
+            };
+            assert_eq!(timeout(fut, after(5)).await, Ok(7));
+
+            let fut = async {
+                after(2).await;
+                Err::<i32, _>("fail")
+            };
+            assert_eq!(timeout(fut, after(5)).await, Err(TimeoutOr::Err("fail")));
+        });
+    }
+
+    #[test]
+    fn test_timeout_elapses() {
+        block_on(async {
+            let fut = async {
+                after(5).await;
+                Ok::<_, &str>(7)
+            };
+            let res = timeout(fut, after(2)).await;
+            assert_eq!(res, Err(TimeoutOr::Timeout));
+            assert_eq!(
+                res.map_err(|err| err.into_error(|| "timed out")),
+                Err("timed out")
+            );
+        });
+    }
+
+    #[test]
+    fn test_race_timeout() {
+        block_on(async {
+            let work = async {
+                after(2).await;
+                Ok::<_, &str>(7)
+            };
+            let res = race_timeout(work, after(5), || unreachable!()).await;
+            assert_eq!(res, Ok(7));
+
+            let work = async {
+                after(2).await;
+                Err::<i32, _>("fail")
+            };
+            let res = race_timeout(work, after(5), || unreachable!()).await;
+            assert_eq!(res, Err("fail"));
+
+            let work = async {
+                after(5).await;
+                Ok(7)
+            };
+            let res = race_timeout(work, after(2), || "timed out").await;
+            assert_eq!(res, Err("timed out"));
+        });
+    }
+
+    #[test]
+    fn test_cancel_timeout_drops_both() {
+        let fut_dropped = Cell::new(false);
+        let deadline_dropped = Cell::new(false);
+        {
+            let flag = DropFlag(&fut_dropped);
+            let fut = async move {
+                let _flag = flag;
+                after(3).await;
+                Ok::<_, ()>(1)
+            };
+            let flag = DropFlag(&deadline_dropped);
+            let deadline = async move {
+                let _flag = flag;
+                after(3).await;
+            };
+            let mut fut = pin!(timeout(fut, deadline));
+            assert!(poll_once(fut.as_mut()).is_pending());
+        }
+        assert!(fut_dropped.get());
+        assert!(deadline_dropped.get());
+    }
+}
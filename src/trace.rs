@@ -0,0 +1,244 @@
+//! [`tracing`] integration for results.
+//!
+//! Requires the `tracing` feature.
+
+use core::fmt;
+use core::future::Future;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+
+use pin_project_lite::pin_project;
+use tracing::{Level, Span};
+
+use crate::state::{State, Step};
+
+/// Asynchronous [`tracing`] extensions for [`Result<T, E>`].
+pub trait AsyncResultTraceExt<T, E> {
+    /// Emits a `tracing` event at `level` when the result is `Err`.
+    ///
+    /// The event message is produced by awaiting `make_event` with a reference to
+    /// the error, so it can include asynchronously fetched context. The result is
+    /// returned unchanged; on `Ok`, `make_event` is never called.
+    ///
+    /// ```
+    /// use async_result_ext::trace::AsyncResultTraceExt;
+    /// use tracing::Level;
+    ///
+    /// # futures::executor::block_on(async {
+    /// let r: Result<i32, &str> = Err("connection reset");
+    /// let res = r
+    ///     .async_trace_err(Level::WARN, |e| {
+    ///         let e = e.to_string();
+    ///         async move { format!("upload failed: {e}") }
+    ///     })
+    ///     .await;
+    /// assert_eq!(res, Err("connection reset"));
+    /// # });
+    /// ```
+    fn async_trace_err<M, F, Fut>(self, level: Level, make_event: F) -> AsyncTraceErr<T, E, F, Fut>
+    where
+        M: fmt::Display,
+        F: FnOnce(&E) -> Fut,
+        Fut: Future<Output = M>;
+
+    /// Like [`async_map`](crate::AsyncResultExt::async_map), but runs `op` inside `span`.
+    ///
+    /// `span` is entered while `op` is called and every time its future is polled,
+    /// so events recorded by `op` are attributed to it without a separate
+    /// `.instrument()` call.
+    ///
+    /// ```
+    /// use async_result_ext::trace::AsyncResultTraceExt;
+    ///
+    /// # futures::executor::block_on(async {
+    /// let r: Result<i32, &str> = Ok(2);
+    /// let span = tracing::info_span!("double");
+    /// let res = r.async_map_instrumented(span, |v| async move { v * 2 }).await;
+    /// assert_eq!(res, Ok(4));
+    /// # });
+    /// ```
+    fn async_map_instrumented<U, F, Fut>(
+        self,
+        span: Span,
+        op: F,
+    ) -> AsyncMapInstrumented<T, E, F, Fut>
+    where
+        F: FnOnce(T) -> Fut,
+        Fut: Future<Output = U>;
+}
+
+impl<T, E> AsyncResultTraceExt<T, E> for Result<T, E> {
+    fn async_trace_err<M, F, Fut>(self, level: Level, make_event: F) -> AsyncTraceErr<T, E, F, Fut>
+    where
+        M: fmt::Display,
+        F: FnOnce(&E) -> Fut,
+        Fut: Future<Output = M>,
+    {
+        AsyncTraceErr {
+            state: State::new((self, level, make_event)),
+        }
+    }
+
+    fn async_map_instrumented<U, F, Fut>(
+        self,
+        span: Span,
+        op: F,
+    ) -> AsyncMapInstrumented<T, E, F, Fut>
+    where
+        F: FnOnce(T) -> Fut,
+        Fut: Future<Output = U>,
+    {
+        AsyncMapInstrumented {
+            span,
+            state: State::new((self, op)),
+        }
+    }
+}
+
+pin_project! {
+    /// Future for the [`async_trace_err`](AsyncResultTraceExt::async_trace_err) method.
+    #[must_use = "futures do nothing unless you `.await` or poll them"]
+    pub struct AsyncTraceErr<T, E, F, Fut> {
+        #[pin]
+        state: State<(Result<T, E>, Level, F), Fut, (Level, E)>,
+    }
+}
+
+impl<T, E, M, F, Fut> Future for AsyncTraceErr<T, E, F, Fut>
+where
+    M: fmt::Display,
+    F: FnOnce(&E) -> Fut,
+    Fut: Future<Output = M>,
+{
+    type Output = Result<T, E>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        self.project().state.poll_with(
+            "async_trace_err",
+            cx,
+            |(this, level, make_event)| match this {
+                Ok(value) => Step::Ready(Ok(value)),
+                Err(err) => Step::Run(make_event(&err), (level, err)),
+            },
+            |message, (level, err)| {
+                emit(level, &message);
+                Err(err)
+            },
+        )
+    }
+}
+
+pin_project! {
+    /// Future for the [`async_map_instrumented`](AsyncResultTraceExt::async_map_instrumented)
+    /// method.
+    #[must_use = "futures do nothing unless you `.await` or poll them"]
+    pub struct AsyncMapInstrumented<T, E, F, Fut> {
+        span: Span,
+        #[pin]
+        state: State<(Result<T, E>, F), Fut, ()>,
+    }
+}
+
+impl<T, E, U, F, Fut> Future for AsyncMapInstrumented<T, E, F, Fut>
+where
+    F: FnOnce(T) -> Fut,
+    Fut: Future<Output = U>,
+{
+    type Output = Result<U, E>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.project();
+        let _entered = this.span.enter();
+        this.state.poll_with(
+            "async_map_instrumented",
+            cx,
+            |(this, op)| match this {
+                Ok(value) => Step::Run(op(value), ()),
+                Err(err) => Step::Ready(Err(err)),
+            },
+            |output, ()| Ok(output),
+        )
+    }
+}
+
+/// `tracing::event!` needs a constant level, so dispatch on it here.
+fn emit(level: Level, message: &dyn fmt::Display) {
+    match level {
+        Level::ERROR => tracing::event!(Level::ERROR, "{message}"),
+        Level::WARN => tracing::event!(Level::WARN, "{message}"),
+        Level::INFO => tracing::event!(Level::INFO, "{message}"),
+        Level::DEBUG => tracing::event!(Level::DEBUG, "{message}"),
+        Level::TRACE => tracing::event!(Level::TRACE, "{message}"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures::executor::block_on;
+    use std::format;
+    use std::string::{String, ToString};
+    use tracing_test::traced_test;
+
+    #[test]
+    #[traced_test]
+    fn test_async_trace_err_on_err() {
+        block_on(async {
+            let r: Result<i32, &str> = Err("boom");
+            let res = r
+                .async_trace_err(Level::WARN, |e| {
+                    let e = e.to_string();
+                    async move { format!("request failed: {e}") }
+                })
+                .await;
+            assert_eq!(res, Err("boom"));
+        });
+        assert!(logs_contain("WARN"));
+        assert!(logs_contain("request failed: boom"));
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_async_trace_err_on_ok() {
+        block_on(async {
+            let r: Result<i32, &str> = Ok(1);
+            let res = r
+                .async_trace_err(Level::ERROR, |_| async { "should not be logged" })
+                .await;
+            assert_eq!(res, Ok(1));
+        });
+        assert!(!logs_contain("should not be logged"));
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_async_map_instrumented() {
+        block_on(async {
+            let current = || Span::current().metadata().map(|meta| meta.name());
+
+            let r: Result<i32, &str> = Ok(2);
+            let span = tracing::info_span!("enrich");
+            let res = r
+                .async_map_instrumented(span, |v| {
+                    assert_eq!(current(), Some("enrich"));
+                    async move {
+                        tracing::info!("enriching {v}");
+                        assert_eq!(current(), Some("enrich"));
+                        v * 2
+                    }
+                })
+                .await;
+            assert_eq!(res, Ok(4));
+            assert_ne!(current(), Some("enrich"));
+
+            let r: Result<i32, &str> = Err("fail");
+            let span = tracing::info_span!("enrich");
+            let res: Result<i32, &str> = r
+                .async_map_instrumented(span, |_| crate::tests::never_polled())
+                .await;
+            assert_eq!(res, Err("fail"));
+        });
+        assert!(logs_contain("enrich"));
+        assert!(logs_contain("enriching 2"));
+    }
+}